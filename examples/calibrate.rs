@@ -0,0 +1,32 @@
+//! Prints the suggested tolerance of each assertion call site recorded in a calibration file.
+//!
+//! Record a calibration file by running the tests with `NEARLY_EQ_CALIBRATE` set, then run
+//! `cargo run --example calibrate -- <file> [safety-factor]`.
+
+extern crate nearly_eq;
+
+use std::env;
+use std::process;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let path = match args.next() {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: calibrate <file> [safety-factor]");
+            process::exit(2);
+        }
+    };
+    let safety_factor = match args.next().map(|f| f.parse::<f64>()) {
+        None => 2.0,
+        Some(Ok(f)) => f,
+        Some(Err(e)) => {
+            eprintln!("invalid safety factor: {}", e);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = nearly_eq::print_calibration(&path, safety_factor) {
+        eprintln!("cannot read {}: {}", path, e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,191 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+use std::ops::Deref;
+
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use {DefaultDiff, DefaultTolerance, Nearly, NearlyEq};
+
+macro_rules! wrapper {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default)]
+        #[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+        pub struct $name<T>(#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))] pub T);
+
+        impl<T> $name<T> {
+            /// Returns the wrapped value.
+            #[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    }
+}
+
+wrapper! {
+    /// Wrapper that compares `approx::AbsDiffEq` values with `NearlyEq`.
+    ///
+    /// The diff value is the `epsilon` of `abs_diff_eq`, and defaults to `default_epsilon()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate nearly_eq;
+    /// # use nearly_eq::Approx;
+    /// # fn main() {
+    /// assert_nearly_eq!(Approx(0.1f64 + 0.2), Approx(0.3)); // does not panic
+    /// assert_nearly_eq!(Approx(1f32), Approx(1.05), 0.1); // does not panic
+    /// # }
+    /// ```
+    Approx
+}
+
+wrapper! {
+    /// Wrapper that compares `approx::RelativeEq` values with `NearlyEq`.
+    ///
+    /// The diff value is the `max_relative` of `relative_eq`, and defaults to `default_max_relative()`.
+    /// The absolute `epsilon` is always `default_epsilon()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate nearly_eq;
+    /// # use nearly_eq::ApproxRelative;
+    /// # fn main() {
+    /// assert_nearly_eq!(ApproxRelative(1e10f64), ApproxRelative(1e10 + 1.0), 1e-9); // does not panic
+    /// # }
+    /// ```
+    ApproxRelative
+}
+
+wrapper! {
+    /// Wrapper that compares `approx::UlpsEq` values with `NearlyEq`.
+    ///
+    /// The diff value is the `max_ulps` of `ulps_eq`, and defaults to `default_max_ulps()`.
+    /// The absolute `epsilon` is always `default_epsilon()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate nearly_eq;
+    /// # use nearly_eq::ApproxUlps;
+    /// # fn main() {
+    /// assert_nearly_eq!(ApproxUlps(1f32), ApproxUlps(1.0 + f32::EPSILON), 1); // does not panic
+    /// # }
+    /// ```
+    ApproxUlps
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: AbsDiffEq> DefaultTolerance<T::Epsilon> for Approx<T> {
+    fn default_tolerance() -> T::Epsilon {
+        T::default_epsilon()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: AbsDiffEq> DefaultDiff for Approx<T> {
+    type Diff = T::Epsilon;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: AbsDiffEq> NearlyEq<Approx<T>, T::Epsilon> for Approx<T>
+where
+    T::Epsilon: Clone,
+{
+    fn eq(&self, other: &Approx<T>, eps: &T::Epsilon) -> bool {
+        self.0.abs_diff_eq(&other.0, eps.clone())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: RelativeEq> DefaultTolerance<T::Epsilon> for ApproxRelative<T> {
+    fn default_tolerance() -> T::Epsilon {
+        T::default_max_relative()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: RelativeEq> DefaultDiff for ApproxRelative<T> {
+    type Diff = T::Epsilon;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: RelativeEq> NearlyEq<ApproxRelative<T>, T::Epsilon> for ApproxRelative<T>
+where
+    T::Epsilon: Clone,
+{
+    fn eq(&self, other: &ApproxRelative<T>, eps: &T::Epsilon) -> bool {
+        self.0.relative_eq(&other.0, T::default_epsilon(), eps.clone())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: UlpsEq> DefaultTolerance<u32> for ApproxUlps<T> {
+    fn default_tolerance() -> u32 {
+        T::default_max_ulps()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: UlpsEq> DefaultDiff for ApproxUlps<T> {
+    type Diff = u32;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T: UlpsEq> NearlyEq<ApproxUlps<T>, u32> for ApproxUlps<T> {
+    fn eq(&self, other: &ApproxUlps<T>, eps: &u32) -> bool {
+        self.0.ulps_eq(&other.0, T::default_epsilon(), *eps)
+    }
+}
+
+/// `Nearly` values can be compared with the `approx` macros, such as `assert_abs_diff_eq!`.
+///
+/// The `epsilon` is the diff value of `NearlyEq`, and defaults to `DefaultTolerance::default_tolerance()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate approx;
+/// # extern crate nearly_eq;
+/// # use nearly_eq::Nearly;
+/// # fn main() {
+/// assert_abs_diff_eq!(Nearly(vec![0.1f64 + 0.2]), Nearly(vec![0.3f64])); // does not panic
+/// assert_abs_diff_eq!(Nearly(1f64), Nearly(1.05f64), epsilon = 0.1); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "approx", since = "0.3.0"))]
+impl<T, U> AbsDiffEq<Nearly<U>> for Nearly<T>
+where
+    T: DefaultDiff + NearlyEq<U, <T as DefaultDiff>::Diff> + DefaultTolerance<<T as DefaultDiff>::Diff> + ?Sized,
+    U: ?Sized,
+{
+    type Epsilon = T::Diff;
+
+    fn default_epsilon() -> T::Diff {
+        T::default_tolerance()
+    }
+
+    fn abs_diff_eq(&self, other: &Nearly<U>, epsilon: T::Diff) -> bool {
+        NearlyEq::eq(&self.0, &other.0, &epsilon)
+    }
+}
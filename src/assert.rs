@@ -6,7 +6,10 @@
 /// Asserts that two expressions are nearly(approximately) equal to each other.
 ///
 /// You can optionally add an optional diff value. If you don't supply
-///  a diff value as an argument, NearlyEq::eps() is the default used.
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every comparison is passed to the global `Reporter`, if one is set.
+/// When collections are not nearly equal, the panic message includes their `ComparisonStats`.
 ///
 /// # Examples
 ///
@@ -29,29 +32,28 @@ macro_rules! assert_nearly_eq {
     ($a:expr, $b:expr) => ({
         let (a, b) = (&$a, &$b);
         #[inline(always)]
-        fn nearly_eq_noeps<A: ?Sized, B, C: $crate::NearlyEq<A, B> + ?Sized>(a: &C, b: &A) -> bool {
-            a.eq(b, &C::eps())
+        fn nearly_eq_eps<A: ?Sized, B, C: $crate::NearlyEq<A, B> + $crate::DefaultTolerance<B> + ?Sized>(_: &C, _: &A) -> B {
+            C::default_tolerance()
+        }
+        let eps = nearly_eq_eps(a, b);
+        if !$crate::__check_default(a, b, &eps) {
+            panic!("assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`){}",
+                   *a, *b, $crate::__details(a, b, &eps));
         }
-        assert!(nearly_eq_noeps(a, b),
-                "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`)",
-                 *a, *b);
     });
     ($a:expr, $b:expr, $eps:expr) => ({
         let (a, b, eps) = (&$a, &$b, &$eps);
-        #[inline(always)]
-        fn nearly_eq<A: ?Sized, B, C: $crate::NearlyEq<A, B> + ?Sized>(a: &C, b: &A, c: &B) -> bool {
-            a.eq(b, c)
+        if !$crate::__check(a, b, eps) {
+            panic!("assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`){}",
+                   *a, *b, *eps, $crate::__details(a, b, eps));
         }
-        assert!(nearly_eq(a, b, eps),
-                "assertion failed: `(left == right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`)",
-                 *a, *b, $eps);
     })
 }
 
 /// Asserts that two expressions are nearly(approximately) equal to each other.
 ///
 /// You can optionally add an optional diff value. If you don't supply
-///  a diff value as an argument, NearlyEq::eps() is the default used.
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
 ///
 /// Unlike assert_nearly_eq!, debug_assert_nearly_eq! statements are only enabled in non optimized builds by default.
 /// An optimized build will omit all debug_assert_nearly_eq! statements unless -C debug-assertions is passed to the compiler.
@@ -76,3 +78,363 @@ macro_rules! assert_nearly_eq {
 macro_rules! debug_assert_nearly_eq {
     ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_eq!($($arg)*); })
 }
+
+/// Asserts that the first expression is less than or nearly(approximately) equal to the second.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every comparison is passed to the global `Reporter`, if one is set.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_le!(1f64, 2f64); // does not panic
+/// assert_nearly_le!(1f64 + 1e-13, 1f64); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_le!(1.1f64, 1f64, 0.05f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_le {
+    ($a:expr, $b:expr) => ({
+        let (a, b) = (&$a, &$b);
+        #[inline(always)]
+        fn nearly_eq_eps<A: ?Sized, B, C: $crate::NearlyOrd<A, B> + $crate::DefaultTolerance<B> + ?Sized>(_: &C, _: &A) -> B {
+            C::default_tolerance()
+        }
+        let eps = nearly_eq_eps(a, b);
+        if !$crate::__check_order_default(a, b, &eps, ::std::cmp::Ordering::Less) {
+            panic!("assertion failed: `(left <= right)` (left: `{:?}` , right: `{:?}`)", *a, *b);
+        }
+    });
+    ($a:expr, $b:expr, $eps:expr) => ({
+        let (a, b, eps) = (&$a, &$b, &$eps);
+        if !$crate::__check_order(a, b, eps, ::std::cmp::Ordering::Less) {
+            panic!("assertion failed: `(left <= right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`)",
+                   *a, *b, *eps);
+        }
+    })
+}
+
+/// Asserts that the first expression is greater than or nearly(approximately) equal to the second.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every comparison is passed to the global `Reporter`, if one is set.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_ge!(2f64, 1f64); // does not panic
+/// assert_nearly_ge!(1f64, 1f64 + 1e-13); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_ge!(1f64, 1.1f64, 0.05f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_ge {
+    ($a:expr, $b:expr) => ({
+        let (a, b) = (&$a, &$b);
+        #[inline(always)]
+        fn nearly_eq_eps<A: ?Sized, B, C: $crate::NearlyOrd<A, B> + $crate::DefaultTolerance<B> + ?Sized>(_: &C, _: &A) -> B {
+            C::default_tolerance()
+        }
+        let eps = nearly_eq_eps(a, b);
+        if !$crate::__check_order_default(a, b, &eps, ::std::cmp::Ordering::Greater) {
+            panic!("assertion failed: `(left >= right)` (left: `{:?}` , right: `{:?}`)", *a, *b);
+        }
+    });
+    ($a:expr, $b:expr, $eps:expr) => ({
+        let (a, b, eps) = (&$a, &$b, &$eps);
+        if !$crate::__check_order(a, b, eps, ::std::cmp::Ordering::Greater) {
+            panic!("assertion failed: `(left >= right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`)",
+                   *a, *b, *eps);
+        }
+    })
+}
+
+/// Asserts that the first expression is less than or nearly(approximately) equal to the second.
+///
+/// Unlike assert_nearly_le!, debug_assert_nearly_le! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_le {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_le!($($arg)*); })
+}
+
+/// Asserts that the first expression is greater than or nearly(approximately) equal to the second.
+///
+/// Unlike assert_nearly_ge!, debug_assert_nearly_ge! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_ge {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_ge!($($arg)*); })
+}
+
+/// Asserts that the elements of a collection are nearly(approximately) sorted in non-decreasing order.
+///
+/// An element may be less than the previous one if they are nearly equal. You can optionally
+/// add a diff value. If you don't supply a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_sorted!([0.1f64, 0.5, 0.5 - 1e-13, 1.0]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_sorted!([0.1f64, 0.5, 0.4, 1.0]); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_sorted {
+    ($v:expr) => (assert_nearly_monotonic!($v, $crate::Monotonic::Increasing));
+    ($v:expr, $eps:expr) => (assert_nearly_monotonic!($v, $crate::Monotonic::Increasing, $eps));
+}
+
+/// Asserts that the elements of a collection are nearly(approximately) monotonic in the given `Monotonic` order.
+///
+/// You can optionally add a diff value. If you don't supply
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every assertion is passed to the global `Reporter`, if one is set.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Monotonic;
+/// # fn main() {
+/// assert_nearly_monotonic!(vec![3f64, 2.0, 2.0 + 1e-13], Monotonic::Decreasing); // does not panic
+/// assert_nearly_monotonic!(vec![1f64, 1.5, 2.0], Monotonic::StrictlyIncreasing, 0.1); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Monotonic;
+/// # fn main() {
+/// assert_nearly_monotonic!(vec![1f64, 1.05, 2.0], Monotonic::StrictlyIncreasing, 0.1); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_monotonic {
+    ($v:expr, $order:expr) => ({
+        let v = &$v;
+        #[inline(always)]
+        fn nearly_eq_eps<B, S: $crate::Sequence + ?Sized>(_: &S) -> B
+        where
+            S::Item: $crate::NearlyEq<S::Item, B> + $crate::DefaultTolerance<B>,
+        {
+            <S::Item as $crate::DefaultTolerance<B>>::default_tolerance()
+        }
+        let eps = nearly_eq_eps(v);
+        if let Some(violation) = $crate::__check_monotonic_default(v, &eps, $order) {
+            panic!("assertion failed: {} (values: `{:?}`)", violation, *v);
+        }
+    });
+    ($v:expr, $order:expr, $eps:expr) => ({
+        let (v, eps) = (&$v, &$eps);
+        if let Some(violation) = $crate::__check_monotonic(v, eps, $order) {
+            panic!("assertion failed: {} (values: `{:?}`, eps: `{:?}`)", violation, *v, *eps);
+        }
+    })
+}
+
+/// Asserts that the elements of a collection are nearly(approximately) sorted in non-decreasing order.
+///
+/// Unlike assert_nearly_sorted!, debug_assert_nearly_sorted! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_sorted {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_sorted!($($arg)*); })
+}
+
+/// Asserts that the elements of a collection are nearly(approximately) monotonic in the given `Monotonic` order.
+///
+/// Unlike assert_nearly_monotonic!, debug_assert_nearly_monotonic! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_monotonic {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_monotonic!($($arg)*); })
+}
+
+/// Asserts that an expression is nearly(approximately) equal to zero.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every assertion is passed to the global `Reporter`, if one is set.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_zero!(1e-13f64); // does not panic
+/// assert_nearly_zero!(0.5f64, 0.6f64); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_zero!(1e-3f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_zero {
+    ($a:expr) => ({
+        let a = &$a;
+        #[inline(always)]
+        fn nearly_eq_eps<B, C: $crate::NearlyZero<B> + $crate::DefaultTolerance<B> + ?Sized>(_: &C) -> B {
+            C::default_tolerance()
+        }
+        let eps = nearly_eq_eps(a);
+        if !$crate::__check_target_default(a, &eps, $crate::Target::Zero) {
+            panic!("assertion failed: `value is nearly zero` (value: `{:?}`)", *a);
+        }
+    });
+    ($a:expr, $eps:expr) => ({
+        let (a, eps) = (&$a, &$eps);
+        if !$crate::__check_target(a, eps, $crate::Target::Zero) {
+            panic!("assertion failed: `value is nearly zero` (value: `{:?}`, eps: `{:?}`)", *a, *eps);
+        }
+    })
+}
+
+/// Asserts that an expression is nearly(approximately) equal to an integer.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every assertion is passed to the global `Reporter`, if one is set.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_integer!(2.9999999f32); // does not panic
+/// assert_nearly_integer!(2.4f64, 0.5f64); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_integer!(2.5f64); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_integer {
+    ($a:expr) => ({
+        let a = &$a;
+        #[inline(always)]
+        fn nearly_eq_eps<B, C: $crate::NearlyZero<B> + $crate::DefaultTolerance<B> + ?Sized>(_: &C) -> B {
+            C::default_tolerance()
+        }
+        let eps = nearly_eq_eps(a);
+        if !$crate::__check_target_default(a, &eps, $crate::Target::Integer) {
+            panic!("assertion failed: `value is nearly an integer` (value: `{:?}`)", *a);
+        }
+    });
+    ($a:expr, $eps:expr) => ({
+        let (a, eps) = (&$a, &$eps);
+        if !$crate::__check_target(a, eps, $crate::Target::Integer) {
+            panic!("assertion failed: `value is nearly an integer` (value: `{:?}`, eps: `{:?}`)", *a, *eps);
+        }
+    })
+}
+
+/// Asserts that an expression is nearly(approximately) equal to one.
+///
+/// You can optionally add an optional diff value. If you don't supply
+///  a diff value as an argument, DefaultTolerance::default_tolerance() is the default used.
+///
+/// Every assertion is passed to the global `Reporter`, if one is set.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_one!(vec![1f64, 1.0 + 1e-13]); // does not panic
+/// assert_nearly_one!(0.7f64, 0.5f64); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # fn main() {
+/// assert_nearly_one!(vec![1f64, 1.1]); // panics
+/// # }
+/// ```
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! assert_nearly_one {
+    ($a:expr) => ({
+        let a = &$a;
+        #[inline(always)]
+        fn nearly_eq_eps<B, C: $crate::NearlyZero<B> + $crate::DefaultTolerance<B> + ?Sized>(_: &C) -> B {
+            C::default_tolerance()
+        }
+        let eps = nearly_eq_eps(a);
+        if !$crate::__check_target_default(a, &eps, $crate::Target::One) {
+            panic!("assertion failed: `value is nearly one` (value: `{:?}`)", *a);
+        }
+    });
+    ($a:expr, $eps:expr) => ({
+        let (a, eps) = (&$a, &$eps);
+        if !$crate::__check_target(a, eps, $crate::Target::One) {
+            panic!("assertion failed: `value is nearly one` (value: `{:?}`, eps: `{:?}`)", *a, *eps);
+        }
+    })
+}
+
+/// Asserts that an expression is nearly(approximately) equal to zero.
+///
+/// Unlike assert_nearly_zero!, debug_assert_nearly_zero! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_zero {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_zero!($($arg)*); })
+}
+
+/// Asserts that an expression is nearly(approximately) equal to an integer.
+///
+/// Unlike assert_nearly_integer!, debug_assert_nearly_integer! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_integer {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_integer!($($arg)*); })
+}
+
+/// Asserts that an expression is nearly(approximately) equal to one.
+///
+/// Unlike assert_nearly_one!, debug_assert_nearly_one! statements are only enabled in non optimized builds by default.
+#[macro_export]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+macro_rules! debug_assert_nearly_one {
+    ($($arg:tt)*) => (if cfg!(debug_assertions) { assert_nearly_one!($($arg)*); })
+}
@@ -0,0 +1,192 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+
+use Deviation;
+
+/// Environment variable enabling calibration, holding the file the observed differences are appended to.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub const CALIBRATE_ENV: &str = "NEARLY_EQ_CALIBRATE";
+
+/// The largest differences observed at one assertion call site.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Calibration {
+    /// The source file of the assertion.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub file: String,
+    /// The line of the assertion.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub line: u32,
+    /// The column of the assertion.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub column: u32,
+    /// The number of comparisons observed.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub count: u64,
+    /// The largest differences observed.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub deviation: Deviation,
+}
+
+impl Calibration {
+    /// Returns the suggested tolerance, the observed differences multiplied by `safety_factor`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn suggest(&self, safety_factor: f64) -> Deviation {
+        Deviation {
+            abs: self.deviation.abs * safety_factor,
+            rel: self.deviation.rel * safety_factor,
+            ulps: self
+                .deviation
+                .ulps
+                .map(|ulps| (ulps as f64 * safety_factor).ceil() as u64),
+        }
+    }
+
+    fn merge(&mut self, other: &Calibration) {
+        self.count += other.count;
+        self.deviation = self.deviation.max(other.deviation);
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+static FROM_ENV: Once = Once::new();
+
+/// Starts recording the differences observed at each assertion call site.
+///
+/// Each observed difference is appended to `path` as it is made, and
+/// `read_calibration` merges the records of each call site.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn enable_calibration<P: AsRef<Path>>(path: P) {
+    *PATH.lock().unwrap_or_else(|e| e.into_inner()) = Some(path.as_ref().to_path_buf());
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Stops recording differences.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn disable_calibration() {
+    ENABLED.store(false, Ordering::SeqCst);
+}
+
+/// Reads a calibration file, merging the records of each call site.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn read_calibration<P: AsRef<Path>>(path: P) -> io::Result<Vec<Calibration>> {
+    let mut sites: BTreeMap<(String, u32, u32), Calibration> = BTreeMap::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let calibration = parse_line(&line).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid calibration record: {}", line),
+            )
+        })?;
+        let key = (
+            calibration.file.clone(),
+            calibration.line,
+            calibration.column,
+        );
+        if let Some(site) = sites.get_mut(&key) {
+            site.merge(&calibration);
+            continue;
+        }
+        sites.insert(key, calibration);
+    }
+    Ok(sites.into_values().collect())
+}
+
+/// Prints the suggested tolerance of each call site in a calibration file to stdout.
+///
+/// The observed differences are multiplied by `safety_factor`, which should be above `1.0`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn print_calibration<P: AsRef<Path>>(path: P, safety_factor: f64) -> io::Result<()> {
+    for site in read_calibration(path)? {
+        let suggested = site.suggest(safety_factor);
+        print!(
+            "{}:{}:{}: abs {:e}, rel {:e}",
+            site.file, site.line, site.column, suggested.abs, suggested.rel
+        );
+        if let Some(ulps) = suggested.ulps {
+            print!(", ulps {}", ulps);
+        }
+        println!(" ({} comparison(s))", site.count);
+    }
+    Ok(())
+}
+
+fn parse_line(line: &str) -> Option<Calibration> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 7 {
+        return None;
+    }
+    Some(Calibration {
+        file: fields[0].to_string(),
+        line: fields[1].parse().ok()?,
+        column: fields[2].parse().ok()?,
+        count: fields[3].parse().ok()?,
+        deviation: Deviation {
+            abs: fields[4].parse().ok()?,
+            rel: fields[5].parse().ok()?,
+            ulps: match fields[6] {
+                "-" => None,
+                ulps => Some(ulps.parse().ok()?),
+            },
+        },
+    })
+}
+
+pub(crate) fn init_from_env() {
+    FROM_ENV.call_once(|| {
+        if let Some(path) = env::var_os(CALIBRATE_ENV) {
+            enable_calibration(path);
+        }
+    });
+}
+
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn observe(deviation: Option<Deviation>, location: &'static Location<'static>) {
+    let deviation = match deviation {
+        Some(deviation) => deviation,
+        None => return,
+    };
+    let record = format!(
+        "{}\t{}\t{}\t1\t{:e}\t{:e}\t{}\n",
+        location.file(),
+        location.line(),
+        location.column(),
+        deviation.abs,
+        deviation.rel,
+        deviation
+            .ulps
+            .map_or_else(|| "-".to_string(), |ulps| ulps.to_string())
+    );
+    let path = PATH.lock().unwrap_or_else(|e| e.into_inner());
+    let path = match *path {
+        Some(ref path) => path,
+        None => return,
+    };
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(record.as_bytes()));
+    if let Err(e) = written {
+        eprintln!("nearly_eq: cannot write to {}: {}", path.display(), e);
+    }
+}
@@ -4,15 +4,136 @@
 //! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
 
 use num_complex::Complex;
-use NearlyEq;
+use tolerance::max_tolerance;
+use {max_deviation, max_margin, DefaultDiff, DefaultTolerance, Deviation, Factor, Magnitude, NearlyEq, NearlyKey,
+     NearlyZero, RequiredTolerance, Scalable, Target, Tolerance};
 
-#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.1.2"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<Complex<A>, B> for Complex<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Complex<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.1.2"))]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<Complex<A>, B> for Complex<C> {
     fn eq(&self, other: &Complex<A>, eps: &B) -> bool {
         self.re.eq(&other.re, eps) && self.im.eq(&other.im, eps)
     }
+
+    fn margin(&self, other: &Complex<A>, eps: &B) -> Option<f64> {
+        max_margin([self.re.margin(&other.re, eps), self.im.margin(&other.im, eps)])
+    }
+
+    fn deviation(&self, other: &Complex<A>) -> Option<Deviation> {
+        max_deviation([self.re.deviation(&other.re), self.im.deviation(&other.im)])
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+impl<A, B: PartialOrd, C: RequiredTolerance<A, B>> RequiredTolerance<Complex<A>, B> for Complex<C> {
+    fn required_tolerance(&self, other: &Complex<A>) -> Tolerance<B> {
+        max_tolerance([
+            self.re.required_tolerance(&other.re),
+            self.im.required_tolerance(&other.im),
+        ])
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+impl<A: Magnitude> Magnitude for Complex<A> {
+    fn magnitude(&self) -> f64 {
+        self.re.magnitude().hypot(self.im.magnitude())
+    }
+
+    fn distance(&self, other: &Complex<A>) -> f64 {
+        self.re.distance(&other.re).hypot(self.im.distance(&other.im))
+    }
+}
+
+macro_rules! scalable_impls {
+    ($($T:ident)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+            impl Scalable for Complex<$T> {
+                fn fit<'a, I>(pairs: I, factor: Factor) -> Complex<$T>
+                where
+                    I: IntoIterator<Item = (&'a Complex<$T>, &'a Complex<$T>)>,
+                {
+                    let (dot, norm_sqr) = pairs
+                        .into_iter()
+                        .fold((Complex::new(0.0, 0.0), 0.0), |(dot, norm_sqr), (x, y)| {
+                            (dot + y.conj() * x, norm_sqr + y.norm_sqr())
+                        });
+                    let one = Complex::new(1.0, 0.0);
+                    match factor {
+                        Factor::Sign => if dot.re < 0.0 { -one } else { one },
+                        Factor::Phase => if dot.norm_sqr() == 0.0 { one } else { dot / dot.norm() },
+                        Factor::Scale => {
+                            let scale = dot / norm_sqr;
+                            if scale.norm_sqr() == 0.0 || !scale.is_finite() { one } else { scale }
+                        }
+                    }
+                }
+
+                fn scale_by(&self, factor: &Complex<$T>) -> Complex<$T> {
+                    *self * *factor
+                }
+            }
+        )+
+    }
+}
+
+scalable_impls! { f32 f64 }
+
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+impl<B, C: NearlyZero<B>> NearlyZero<B> for Complex<C> {
+    fn is_nearly_zero(&self, eps: &B) -> bool {
+        self.re.is_nearly_zero(eps) && self.im.is_nearly_zero(eps)
+    }
+
+    fn is_nearly_integer(&self, eps: &B) -> bool {
+        self.re.is_nearly_integer(eps) && self.im.is_nearly_integer(eps)
+    }
+
+    fn is_nearly_one(&self, eps: &B) -> bool {
+        self.re.is_nearly_one(eps) && self.im.is_nearly_zero(eps)
+    }
+
+    fn margin_from(&self, target: Target, eps: &B) -> Option<f64> {
+        let im = if target == Target::One { Target::Zero } else { target };
+        max_margin([self.re.margin_from(target, eps), self.im.margin_from(im, eps)])
+    }
+
+    fn deviation_from(&self, target: Target) -> Option<Deviation> {
+        let im = if target == Target::One { Target::Zero } else { target };
+        max_deviation([self.re.deviation_from(target), self.im.deviation_from(im)])
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+impl<T: NearlyKey> NearlyKey for Complex<T> {
+    type Key = (T::Key, T::Key);
+
+    fn key(&self, cell: f64) -> (T::Key, T::Key) {
+        (self.re.key(cell), self.im.key(cell))
+    }
+
+    fn neighbor_keys(&self, cell: f64) -> Vec<(T::Key, T::Key)> {
+        let im = self.im.neighbor_keys(cell);
+        self.re
+            .neighbor_keys(cell)
+            .into_iter()
+            .flat_map(|re| im.iter().map(move |im| (re.clone(), im.clone())))
+            .collect()
+    }
+
+    fn within(&self, other: &Complex<T>, cell: f64) -> bool {
+        self.re.within(&other.re, cell) && self.im.within(&other.im, cell)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "num-complex", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Complex<C> {
+    type Diff = C::Diff;
 }
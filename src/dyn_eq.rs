@@ -0,0 +1,157 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::any::{type_name, Any};
+use std::error::Error;
+use std::fmt;
+
+use {DefaultTolerance, NearlyEq};
+
+/// Error returned when a dynamic comparison is made against a value of another type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct TypeMismatch {
+    /// The type name of the left value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub expected: &'static str,
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "types differ: the right value is not a `{}`", self.expected)
+    }
+}
+
+impl Error for TypeMismatch {}
+
+/// Object-safe trait for nearly(approximately) equality comparisons of values of any type.
+///
+/// It is implemented for every `NearlyEq<Self, f64> + Any + Debug` type, so values of
+/// different types can be kept as `Box<dyn DynNearlyEq>` and compared with an `f64` diff value.
+/// Values of different types are never nearly equal.
+///
+/// Boxed values are compared by their contents on either side, and `as_any` returns the contents of a box.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::DynNearlyEq;
+/// # fn main() {
+/// let fixtures: Vec<Box<dyn DynNearlyEq>> = vec![Box::new(0.3f64), Box::new(vec![1f64, 2.0])];
+/// let results: Vec<Box<dyn DynNearlyEq>> = vec![Box::new(0.1f64 + 0.2), Box::new(vec![1f64, 2.0 + 1e-12])];
+///
+/// assert!(fixtures[0].dyn_eq(results[0].as_any(), 1e-9));
+/// assert!(!fixtures[0].dyn_eq(results[1].as_any(), 1e-9));
+/// assert_nearly_eq!(fixtures, results, 1e-9); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait DynNearlyEq: Any + fmt::Debug {
+    /// This method returns the value as `&dyn Any`, for use as the right value of a comparison.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn as_any(&self) -> &dyn Any;
+
+    /// This method tests for nearly(approximately) equality,
+    /// returning an error if the other value is of another type.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn try_dyn_eq(&self, other: &dyn Any, eps: f64) -> Result<bool, TypeMismatch>;
+
+    /// This method tests for nearly(approximately) equality.
+    /// Values of different types are not nearly equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn dyn_eq(&self, other: &dyn Any, eps: f64) -> bool {
+        self.try_dyn_eq(other, eps).unwrap_or(false)
+    }
+
+    /// This method returns the difference as a ratio of the diff value, as `NearlyEq::margin` does.
+    ///
+    /// Returns `None` if the other value is of another type.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn dyn_margin(&self, other: &dyn Any, eps: f64) -> Option<f64>;
+
+    /// This method describes the difference for assertion messages, as `NearlyEq::explain` does.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn dyn_explain(&self, other: &dyn Any, eps: f64) -> Option<String>;
+}
+
+// `Box<dyn DynNearlyEq>` is itself a `DynNearlyEq` through the blanket impl,
+// so boxes are looked through on both sides to compare their contents.
+fn boxed<T: Any>(value: &T) -> Option<&dyn DynNearlyEq> {
+    (value as &dyn Any)
+        .downcast_ref::<Box<dyn DynNearlyEq>>()
+        .map(|value| &**value)
+}
+
+fn unbox(value: &dyn Any) -> &dyn Any {
+    match value.downcast_ref::<Box<dyn DynNearlyEq>>() {
+        Some(value) => value.as_any(),
+        None => value,
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T: NearlyEq<T, f64> + Any + fmt::Debug> DynNearlyEq for T {
+    fn as_any(&self) -> &dyn Any {
+        match boxed(self) {
+            Some(inner) => inner.as_any(),
+            None => self,
+        }
+    }
+
+    fn try_dyn_eq(&self, other: &dyn Any, eps: f64) -> Result<bool, TypeMismatch> {
+        if let Some(inner) = boxed(self) {
+            return inner.try_dyn_eq(other, eps);
+        }
+        match unbox(other).downcast_ref::<T>() {
+            Some(other) => Ok(NearlyEq::eq(self, other, &eps)),
+            None => Err(TypeMismatch {
+                expected: type_name::<T>(),
+            }),
+        }
+    }
+
+    fn dyn_margin(&self, other: &dyn Any, eps: f64) -> Option<f64> {
+        if let Some(inner) = boxed(self) {
+            return inner.dyn_margin(other, eps);
+        }
+        unbox(other)
+            .downcast_ref::<T>()
+            .and_then(|other| NearlyEq::margin(self, other, &eps))
+    }
+
+    fn dyn_explain(&self, other: &dyn Any, eps: f64) -> Option<String> {
+        if let Some(inner) = boxed(self) {
+            return inner.dyn_explain(other, eps);
+        }
+        match self.try_dyn_eq(other, eps) {
+            Ok(_) => NearlyEq::explain(self, unbox(other).downcast_ref::<T>()?, &eps),
+            Err(error) => Some(error.to_string()),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl NearlyEq<dyn DynNearlyEq, f64> for dyn DynNearlyEq {
+    fn eq(&self, other: &dyn DynNearlyEq, eps: &f64) -> bool {
+        self.dyn_eq(other.as_any(), *eps)
+    }
+
+    fn margin(&self, other: &dyn DynNearlyEq, eps: &f64) -> Option<f64> {
+        self.dyn_margin(other.as_any(), *eps)
+    }
+
+    fn explain(&self, other: &dyn DynNearlyEq, eps: &f64) -> Option<String> {
+        self.dyn_explain(other.as_any(), *eps)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl DefaultTolerance<f64> for dyn DynNearlyEq {
+    fn default_tolerance() -> f64 {
+        f64::default_tolerance()
+    }
+}
@@ -0,0 +1,181 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::error::Error;
+use std::fmt;
+
+use {max_margin, NearlyEq, Sequence};
+
+/// Error returned when the data and tolerance lengths of an elementwise comparison differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct LengthMismatch {
+    /// The number of left elements.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub left: usize,
+    /// The number of right elements.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub right: usize,
+    /// The number of tolerances.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub tolerances: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lengths differ: {} left element(s), {} right element(s) and {} tolerance(s)",
+            self.left, self.right, self.tolerances
+        )
+    }
+}
+
+impl Error for LengthMismatch {}
+
+/// Compares collections elementwise, with a separate diff value for each element.
+///
+/// The diff value is a slice, `Vec` or array of the same length as the values.
+/// There is no default diff value, so the two-argument assertions do not compile.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Elementwise;
+/// # fn main() {
+/// let measured = vec![1.0f64, 2.05, 3.0];
+/// let expected = vec![1.0f64, 2.0, 3.0];
+/// assert_nearly_eq!(Elementwise::new(&measured), expected, [1e-6, 0.1, 1e-6]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Elementwise;
+/// # fn main() {
+/// let measured = vec![1.0f64, 2.05, 3.0];
+/// let expected = vec![1.0f64, 2.0, 3.0];
+/// assert_nearly_eq!(Elementwise::new(&measured), expected, [1e-6, 1e-6]); // panics, reporting the lengths
+/// # }
+/// ```
+/// ```compile_fail
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Elementwise;
+/// # fn main() {
+/// let measured = vec![1.0f64, 2.05, 3.0];
+/// let expected = vec![1.0f64, 2.0, 3.0];
+/// assert_nearly_eq!(Elementwise::new(&measured), expected); // does not compile
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Elementwise<'a, L: ?Sized + 'a> {
+    values: &'a L,
+}
+
+impl<'a, L: Sequence + ?Sized> Elementwise<'a, L> {
+    /// Compares the values with per-element diff values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(values: &'a L) -> Elementwise<'a, L> {
+        Elementwise { values }
+    }
+
+    /// This method tests for nearly(approximately) equality,
+    /// or returns an error if the lengths of the values and diff values differ.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn try_eq<R, T>(&self, other: &R, eps: &T) -> Result<bool, LengthMismatch>
+    where
+        R: Sequence + ?Sized,
+        T: Sequence + ?Sized,
+        L::Item: NearlyEq<R::Item, T::Item>,
+    {
+        self.check_lengths(other, eps)?;
+        Ok(self
+            .values
+            .elements()
+            .zip(other.elements())
+            .zip(eps.elements())
+            .all(|((x, y), e)| x.eq(y, e)))
+    }
+
+    /// Returns the indices of the elements that are not nearly equal,
+    /// or an error if the lengths of the values and diff values differ.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn mismatches<R, T>(&self, other: &R, eps: &T) -> Result<Vec<usize>, LengthMismatch>
+    where
+        R: Sequence + ?Sized,
+        T: Sequence + ?Sized,
+        L::Item: NearlyEq<R::Item, T::Item>,
+    {
+        self.check_lengths(other, eps)?;
+        Ok(self
+            .values
+            .elements()
+            .zip(other.elements())
+            .zip(eps.elements())
+            .enumerate()
+            .filter(|&(_, ((x, y), e))| x.ne(y, e))
+            .map(|(i, _)| i)
+            .collect())
+    }
+
+    fn check_lengths<R, T>(&self, other: &R, eps: &T) -> Result<(), LengthMismatch>
+    where
+        R: Sequence + ?Sized,
+        T: Sequence + ?Sized,
+    {
+        let (left, right, tolerances) = (self.values.len(), other.len(), eps.len());
+        if left == right && left == tolerances {
+            Ok(())
+        } else {
+            Err(LengthMismatch {
+                left,
+                right,
+                tolerances,
+            })
+        }
+    }
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for Elementwise<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, L, R, T> NearlyEq<R, T> for Elementwise<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence + ?Sized,
+    T: Sequence,
+    L::Item: NearlyEq<R::Item, T::Item>,
+{
+    fn eq(&self, other: &R, eps: &T) -> bool {
+        self.try_eq(other, eps).unwrap_or(false)
+    }
+
+    fn margin(&self, other: &R, eps: &T) -> Option<f64> {
+        self.check_lengths(other, eps).ok()?;
+        max_margin(
+            self.values
+                .elements()
+                .zip(other.elements())
+                .zip(eps.elements())
+                .map(|((x, y), e)| x.margin(y, e)),
+        )
+    }
+
+    fn explain(&self, other: &R, eps: &T) -> Option<String> {
+        match self.mismatches(other, eps) {
+            Ok(mismatches) => Some(format!(
+                "{} mismatch(es) at index {:?}",
+                mismatches.len(),
+                mismatches
+            )),
+            Err(err) => Some(err.to_string()),
+        }
+    }
+}
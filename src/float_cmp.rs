@@ -0,0 +1,176 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+use std::ops::Deref;
+
+use num_traits::Float;
+
+use {deviation_of, margin_ratio, DefaultDiff, DefaultTolerance, Deviation, NearlyEq};
+
+/// Wrapper that compares any `num_traits::Float` type, for generic code and custom float types.
+///
+/// The diff value selects the comparison:
+///
+/// - `T` - absolute difference, like `f32` and `f64`.
+/// - `Relative<T>` - difference relative to the larger magnitude of the two values.
+/// - `MaxUlps` - number of representable values between the two values.
+///
+/// The default diff value is the absolute difference `sqrt(T::epsilon())`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # extern crate num_traits;
+/// # use nearly_eq::{FloatCmp, MaxUlps, Relative};
+/// # use num_traits::Float;
+/// fn check_mean<T: Float + std::fmt::Debug>(values: &[T], expected: T) {
+///     let sum = values.iter().fold(T::zero(), |sum, &x| sum + x);
+///     assert_nearly_eq!(FloatCmp(sum / T::from(values.len()).unwrap()), FloatCmp(expected));
+/// }
+///
+/// # fn main() {
+/// check_mean(&[0.1f32, 0.2, 0.3], 0.2);
+/// check_mean(&[0.1f64, 0.2, 0.3], 0.2);
+/// assert_nearly_eq!(FloatCmp(1e10f64), FloatCmp(1e10 + 1.0), Relative(1e-9)); // does not panic
+/// assert_nearly_eq!(FloatCmp(1f32), FloatCmp(1.0 + f32::EPSILON), MaxUlps(1)); // does not panic
+/// # }
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+pub struct FloatCmp<T>(#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))] pub T);
+
+/// Relative diff value for `FloatCmp`, a fraction of the larger magnitude of the two values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+pub struct Relative<T>(#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))] pub T);
+
+/// ULP diff value for `FloatCmp`, the largest number of representable values between the two values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+pub struct MaxUlps(#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))] pub u64);
+
+impl<T> FloatCmp<T> {
+    /// Returns the wrapped value.
+    #[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for FloatCmp<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FloatCmp<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// Maps the value to its index among the representable values of its sign,
+// so that adjacent values differ by one.
+fn ordered<T: Float>(x: T) -> i128 {
+    let (half, _, _) = T::one().integer_decode();
+    let (_, min_exp, _) = T::min_positive_value().integer_decode();
+    let (mantissa, exp, sign) = x.integer_decode();
+    let index = if mantissa == 0 {
+        0
+    } else if exp >= min_exp {
+        (exp - min_exp) as i128 * half as i128 + mantissa as i128
+    } else {
+        (mantissa >> (min_exp - exp)) as i128
+    };
+    if sign < 0 {
+        -index
+    } else {
+        index
+    }
+}
+
+fn ulps<T: Float>(a: T, b: T) -> Option<u64> {
+    if a.is_nan() || b.is_nan() {
+        None
+    } else if a == b {
+        Some(0)
+    } else {
+        Some((ordered(a) - ordered(b)).unsigned_abs().min(u64::MAX as u128) as u64)
+    }
+}
+
+fn to_f64<T: Float>(x: T) -> f64 {
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+impl<T: Float> DefaultTolerance<T> for FloatCmp<T> {
+    fn default_tolerance() -> T {
+        T::epsilon().sqrt()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+impl<T: Float> DefaultDiff for FloatCmp<T> {
+    type Diff = T;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+impl<T: Float> NearlyEq<FloatCmp<T>, T> for FloatCmp<T> {
+    fn eq(&self, other: &FloatCmp<T>, eps: &T) -> bool {
+        self.0 == other.0 || (self.0 - other.0).abs() < *eps
+    }
+
+    fn margin(&self, other: &FloatCmp<T>, eps: &T) -> Option<f64> {
+        if self.0 == other.0 {
+            Some(0.0)
+        } else {
+            margin_ratio(to_f64((self.0 - other.0).abs()), to_f64(*eps))
+        }
+    }
+
+    fn deviation(&self, other: &FloatCmp<T>) -> Option<Deviation> {
+        deviation_of(to_f64(self.0), to_f64(other.0), ulps(self.0, other.0))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+impl<T: Float> NearlyEq<FloatCmp<T>, Relative<T>> for FloatCmp<T> {
+    fn eq(&self, other: &FloatCmp<T>, eps: &Relative<T>) -> bool {
+        self.0 == other.0 || (self.0 - other.0).abs() < eps.0 * self.0.abs().max(other.0.abs())
+    }
+
+    fn margin(&self, other: &FloatCmp<T>, eps: &Relative<T>) -> Option<f64> {
+        if self.0 == other.0 {
+            Some(0.0)
+        } else {
+            let diff = (self.0 - other.0).abs() / self.0.abs().max(other.0.abs());
+            margin_ratio(to_f64(diff), to_f64(eps.0))
+        }
+    }
+
+    fn deviation(&self, other: &FloatCmp<T>) -> Option<Deviation> {
+        deviation_of(to_f64(self.0), to_f64(other.0), ulps(self.0, other.0))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "float", since = "0.3.0"))]
+impl<T: Float> NearlyEq<FloatCmp<T>, MaxUlps> for FloatCmp<T> {
+    fn eq(&self, other: &FloatCmp<T>, eps: &MaxUlps) -> bool {
+        ulps(self.0, other.0).is_some_and(|ulps| ulps <= eps.0)
+    }
+
+    fn margin(&self, other: &FloatCmp<T>, eps: &MaxUlps) -> Option<f64> {
+        ulps(self.0, other.0).and_then(|ulps| margin_ratio(ulps as f64, eps.0 as f64))
+    }
+
+    fn deviation(&self, other: &FloatCmp<T>) -> Option<Deviation> {
+        deviation_of(to_f64(self.0), to_f64(other.0), ulps(self.0, other.0))
+    }
+}
@@ -9,38 +9,110 @@ use num_traits::identities::One;
 use typenum::{Cmp, Greater, Less, U0, U8, U16, U32, Unsigned};
 use fpa::*;
 
-use NearlyEq;
+use {deviation_of, margin_ratio, DefaultDiff, DefaultTolerance, Deviation, NearlyEq, NearlyOrd, NearlyZero,
+     RequiredTolerance, Tolerance};
 
 macro_rules! impl_fpa {
     ($bits:ident, $limit:ident) => {
-        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.2.4"))]
-        impl<FRAC> NearlyEq<Q<$bits, FRAC>, Q<$bits, FRAC>> for Q<$bits, FRAC>
+        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.3.0"))]
+        impl<FRAC> DefaultTolerance for Q<$bits, FRAC>
         where
             FRAC: Cmp<U0, Output = Greater> + Cmp<$limit, Output = Less> + Unsigned,
-            Self: PartialOrd + Clone + Sub<Self, Output=Self>,
         {
-            fn eps() -> Self {
+            fn default_tolerance() -> Self {
                 Self::from_bits(One::one())
             }
+        }
 
+        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.2.4"))]
+        impl<FRAC> NearlyEq<Q<$bits, FRAC>, Q<$bits, FRAC>> for Q<$bits, FRAC>
+        where
+            FRAC: Cmp<U0, Output = Greater> + Cmp<$limit, Output = Less> + Unsigned,
+            Self: PartialOrd + Clone + Sub<Self, Output=Self>,
+        {
             fn eq(&self, other: &Self, eps: &Self) -> bool {
-                println!();
-                println!("self:  {:?} ({})", *self, *self);
-                println!("other: {:?} ({})", other, other);
                 let diff = if *self > *other {
                     self.clone() - other.clone()
                 } else {
                     other.clone() - self.clone()
                 };
-                println!("diff:  {:?} ({})", diff, diff);
-                println!("eps:   {:?} ({})", *eps, *eps);
-                println!();
                 if *self == *other {
                     true
                 } else {
                     diff <= *eps
                 }
             }
+
+            fn margin(&self, other: &Self, eps: &Self) -> Option<f64> {
+                let diff = if *self > *other {
+                    self.clone() - other.clone()
+                } else {
+                    other.clone() - self.clone()
+                };
+                margin_ratio(diff.into_bits() as f64, eps.into_bits() as f64)
+            }
+
+            fn deviation(&self, other: &Self) -> Option<Deviation> {
+                let scale = (FRAC::to_u32() as f64).exp2();
+                let (a, b) = (self.into_bits(), other.into_bits());
+                let ulps = (a as i64 - b as i64).unsigned_abs();
+                deviation_of(a as f64 / scale, b as f64 / scale, Some(ulps))
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.3.0"))]
+        impl<FRAC> RequiredTolerance<Q<$bits, FRAC>, Q<$bits, FRAC>> for Q<$bits, FRAC>
+        where
+            FRAC: Cmp<U0, Output = Greater> + Cmp<$limit, Output = Less> + Unsigned,
+            Self: PartialOrd + Clone + Sub<Self, Output=Self>,
+        {
+            fn required_tolerance(&self, other: &Self) -> Tolerance<Self> {
+                if *self == *other {
+                    Tolerance::Any
+                } else if *self > *other {
+                    Tolerance::AtLeast(self.clone() - other.clone())
+                } else {
+                    Tolerance::AtLeast(other.clone() - self.clone())
+                }
+            }
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.3.0"))]
+        impl<FRAC> NearlyOrd<Q<$bits, FRAC>, Q<$bits, FRAC>> for Q<$bits, FRAC>
+        where
+            FRAC: Cmp<U0, Output = Greater> + Cmp<$limit, Output = Less> + Unsigned,
+            Self: PartialOrd + Clone + Sub<Self, Output=Self>,
+        {
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.3.0"))]
+        impl<FRAC> DefaultDiff for Q<$bits, FRAC>
+        where
+            FRAC: Cmp<U0, Output = Greater> + Cmp<$limit, Output = Less> + Unsigned,
+        {
+            type Diff = Self;
+        }
+
+        #[cfg_attr(feature = "docs", stable(feature = "fpa", since = "0.3.0"))]
+        impl<FRAC> NearlyZero<Q<$bits, FRAC>> for Q<$bits, FRAC>
+        where
+            FRAC: Cmp<U0, Output = Greater> + Cmp<$limit, Output = Less> + Unsigned,
+            Self: PartialOrd + Clone + Sub<Self, Output=Self>,
+        {
+            fn is_nearly_zero(&self, eps: &Self) -> bool {
+                (self.into_bits() as i64).abs() <= eps.into_bits() as i64
+            }
+
+            fn is_nearly_integer(&self, eps: &Self) -> bool {
+                let one = 1i64 << FRAC::to_u32();
+                let fraction = (self.into_bits() as i64).rem_euclid(one);
+                fraction.min(one - fraction) <= eps.into_bits() as i64
+            }
+
+            fn is_nearly_one(&self, eps: &Self) -> bool {
+                let one = 1i64 << FRAC::to_u32();
+                (self.into_bits() as i64 - one).abs() <= eps.into_bits() as i64
+            }
         }
     }
 }
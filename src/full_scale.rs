@@ -0,0 +1,148 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use {margin_ratio, DefaultTolerance, Magnitude, NearlyEq, Norm, Sequence};
+
+/// Compares collections elementwise, with the diff value relative to the full scale.
+///
+/// Each element's difference must be within `eps * full_scale`, where the full scale is
+/// the largest magnitude of the right values unless given explicitly with `full_scale`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::FullScale;
+/// # fn main() {
+/// let measured = vec![0.0005f64, 5.0, 10.004];
+/// assert_nearly_eq!(FullScale::new(&measured), vec![0f64, 5.0, 10.0], 1e-3); // does not panic
+/// assert_nearly_eq!(FullScale::new(&measured).full_scale(20.0), vec![0f64, 5.0, 10.0], 1e-3); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::FullScale;
+/// # fn main() {
+/// let measured = vec![0.02f64, 5.0, 10.0];
+/// assert_nearly_eq!(FullScale::new(&measured), vec![0f64, 5.0, 10.0], 1e-3); // panics
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct FullScale<'a, L: ?Sized + 'a> {
+    values: &'a L,
+    full_scale: Option<f64>,
+}
+
+impl<'a, L: Sequence + ?Sized> FullScale<'a, L>
+where
+    L::Item: Magnitude,
+{
+    /// Compares relative to the largest magnitude of the right values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(values: &'a L) -> FullScale<'a, L> {
+        FullScale {
+            values,
+            full_scale: None,
+        }
+    }
+
+    /// Sets the full scale explicitly.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn full_scale(mut self, full_scale: f64) -> FullScale<'a, L> {
+        self.full_scale = Some(full_scale);
+        self
+    }
+
+    /// Returns the full scale used for the comparison with the right values, `NaN` if any of them is `NaN`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn range<R: Sequence<Item = L::Item> + ?Sized>(&self, other: &R) -> f64 {
+        match self.full_scale {
+            Some(full_scale) => full_scale,
+            None => Norm::LInf.of(other.elements().map(Magnitude::magnitude)),
+        }
+    }
+
+    /// Returns the largest difference and its index, or `None` if the lengths differ or there are no elements.
+    ///
+    /// A `NaN` difference counts as the largest.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn max_error<R: Sequence<Item = L::Item> + ?Sized>(&self, other: &R) -> Option<(f64, usize)> {
+        if self.values.len() != other.len() {
+            return None;
+        }
+        self.values
+            .elements()
+            .zip(other.elements())
+            .map(|(x, y)| x.distance(y))
+            .enumerate()
+            .fold(None, |max: Option<(f64, usize)>, (i, d)| match max {
+                Some((m, _)) if m.is_nan() || d <= m => max,
+                _ => Some((d, i)),
+            })
+    }
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for FullScale<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, L, R> NearlyEq<R, f64> for FullScale<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence<Item = L::Item> + ?Sized,
+    L::Item: Magnitude,
+{
+    fn eq(&self, other: &R, eps: &f64) -> bool {
+        if self.values.len() != other.len() {
+            return false;
+        }
+        let bound = eps * self.range(other);
+        self.values
+            .elements()
+            .zip(other.elements())
+            .all(|(x, y)| x.distance(y) <= bound)
+    }
+
+    fn margin(&self, other: &R, eps: &f64) -> Option<f64> {
+        if self.values.is_empty() && other.is_empty() {
+            return Some(0.0);
+        }
+        let (error, _) = self.max_error(other)?;
+        margin_ratio(error, eps * self.range(other))
+    }
+
+    fn explain(&self, other: &R, eps: &f64) -> Option<String> {
+        if self.values.len() != other.len() {
+            return Some(format!(
+                "lengths differ: {} and {}",
+                self.values.len(),
+                other.len()
+            ));
+        }
+        let (error, index) = self.max_error(other)?;
+        let range = self.range(other);
+        Some(format!(
+            "max error {:e} at index {}, allowed {:e} ({} of full scale {:e})",
+            error,
+            index,
+            eps * range,
+            eps,
+            range
+        ))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, L: Sequence + ?Sized> DefaultTolerance<f64> for FullScale<'a, L> {
+    fn default_tolerance() -> f64 {
+        f64::default_tolerance()
+    }
+}
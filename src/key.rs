@@ -0,0 +1,257 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+/// Trait for values that can be snapped to a grid of cells for hashing and ordering.
+///
+/// Each component is mapped to the index of the cell of the given size that contains it.
+/// Values within one cell size of each other per component are in the same or adjacent cells.
+/// Floats map to `None` if they are `NaN`, which has no adjacent cells.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait NearlyKey {
+    /// The cell index type.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    type Key: Clone + Eq + Hash + Ord;
+
+    /// This method returns the index of the cell containing the value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn key(&self, cell: f64) -> Self::Key;
+
+    /// This method returns the indices of the cell containing the value and all adjacent cells,
+    /// starting with the containing cell.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn neighbor_keys(&self, cell: f64) -> Vec<Self::Key>;
+
+    /// This method tests for each component to differ by at most the cell size.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn within(&self, other: &Self, cell: f64) -> bool;
+}
+
+macro_rules! float_impls {
+    ($($T:ident)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl NearlyKey for $T {
+                type Key = Option<i64>;
+
+                fn key(&self, cell: f64) -> Option<i64> {
+                    if self.is_nan() {
+                        None
+                    } else {
+                        Some((*self as f64 / cell).floor() as i64)
+                    }
+                }
+
+                fn neighbor_keys(&self, cell: f64) -> Vec<Option<i64>> {
+                    match self.key(cell) {
+                        Some(key) => vec![Some(key), Some(key.saturating_sub(1)), Some(key.saturating_add(1))],
+                        None => vec![None],
+                    }
+                }
+
+                fn within(&self, other: &$T, cell: f64) -> bool {
+                    (*self as f64 - *other as f64).abs() <= cell
+                }
+            }
+        )+
+    }
+}
+
+float_impls! { f32 f64 }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T: NearlyKey, const N: usize> NearlyKey for [T; N] {
+    type Key = [T::Key; N];
+
+    fn key(&self, cell: f64) -> [T::Key; N] {
+        std::array::from_fn(|i| self[i].key(cell))
+    }
+
+    fn neighbor_keys(&self, cell: f64) -> Vec<[T::Key; N]> {
+        let mut keys = vec![self.key(cell)];
+        for (i, x) in self.iter().enumerate() {
+            let neighbors = x.neighbor_keys(cell);
+            keys = keys
+                .iter()
+                .flat_map(|key| {
+                    neighbors.iter().map(move |k| {
+                        let mut key = key.clone();
+                        key[i] = k.clone();
+                        key
+                    })
+                })
+                .collect();
+        }
+        keys
+    }
+
+    fn within(&self, other: &[T; N], cell: f64) -> bool {
+        self.iter().zip(other.iter()).all(|(x, y)| x.within(y, cell))
+    }
+}
+
+macro_rules! tuple_impls {
+    ($(($($T:ident $i:tt),+))+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl<$($T: NearlyKey),+> NearlyKey for ($($T,)+) {
+                type Key = ($($T::Key,)+);
+
+                fn key(&self, cell: f64) -> Self::Key {
+                    ($(self.$i.key(cell),)+)
+                }
+
+                fn neighbor_keys(&self, cell: f64) -> Vec<Self::Key> {
+                    let mut keys = vec![self.key(cell)];
+                    $(
+                        let neighbors = self.$i.neighbor_keys(cell);
+                        keys = keys
+                            .iter()
+                            .flat_map(|key| {
+                                neighbors.iter().map(move |k| {
+                                    let mut key = key.clone();
+                                    key.$i = k.clone();
+                                    key
+                                })
+                            })
+                            .collect();
+                    )+
+                    keys
+                }
+
+                fn within(&self, other: &Self, cell: f64) -> bool {
+                    $(self.$i.within(&other.$i, cell))&&+
+                }
+            }
+        )+
+    }
+}
+
+tuple_impls! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+}
+
+/// A value snapped to a grid of cells, for use as a `HashMap`, `HashSet` or `BTreeMap` key.
+///
+/// Equality, hashing and ordering use the cell size and index only, so values in the same cell are equal.
+/// Values that are nearly equal may fall in adjacent cells across a cell boundary;
+/// `find_in` probes the adjacent cells to find them.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use nearly_eq::Quantized;
+/// let mut cache = HashMap::new();
+/// cache.insert(Quantized::new([0.1f64, 0.2], 1e-6), "cached");
+/// assert_eq!(cache.get(&Quantized::new([0.1f64 + 1e-9, 0.2], 1e-6)), Some(&"cached"));
+/// assert_eq!(Quantized::new([0.1f64 - 2e-7, 0.2], 1e-6).find_in(&cache), Some(&"cached"));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Quantized<T: NearlyKey> {
+    value: T,
+    cell: f64,
+    key: T::Key,
+}
+
+impl<T: NearlyKey> Quantized<T> {
+    /// Snaps the value to a grid of the given cell size.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(value: T, cell: f64) -> Quantized<T> {
+        let key = value.key(cell);
+        Quantized { value, cell, key }
+    }
+
+    /// Returns the value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the value, consuming the key.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the cell size.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn cell(&self) -> f64 {
+        self.cell
+    }
+
+    /// Returns the cell index.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn key(&self) -> &T::Key {
+        &self.key
+    }
+
+    /// Returns keys for the cell of the value and all adjacent cells, starting with the cell of the value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn neighbors(&self) -> Vec<Quantized<T>>
+    where
+        T: Clone,
+    {
+        self.value
+            .neighbor_keys(self.cell)
+            .into_iter()
+            .map(|key| Quantized {
+                value: self.value.clone(),
+                cell: self.cell,
+                key,
+            })
+            .collect()
+    }
+
+    /// Looks up an entry whose key is within one cell size of the value, probing the adjacent cells.
+    ///
+    /// Entries in the cell of the value are preferred.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn find_in<'a, V, S: BuildHasher>(&self, map: &'a HashMap<Quantized<T>, V, S>) -> Option<&'a V>
+    where
+        T: Clone,
+    {
+        self.neighbors().iter().find_map(|probe| match map.get_key_value(probe) {
+            Some((key, value)) if key.value.within(&self.value, self.cell) => Some(value),
+            _ => None,
+        })
+    }
+}
+
+impl<T: NearlyKey> PartialEq for Quantized<T> {
+    fn eq(&self, other: &Quantized<T>) -> bool {
+        self.cell.to_bits() == other.cell.to_bits() && self.key == other.key
+    }
+}
+
+impl<T: NearlyKey> Eq for Quantized<T> {}
+
+impl<T: NearlyKey> Hash for Quantized<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cell.to_bits().hash(state);
+        self.key.hash(state)
+    }
+}
+
+impl<T: NearlyKey> PartialOrd for Quantized<T> {
+    fn partial_cmp(&self, other: &Quantized<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: NearlyKey> Ord for Quantized<T> {
+    fn cmp(&self, other: &Quantized<T>) -> Ordering {
+        self.cell
+            .total_cmp(&other.cell)
+            .then_with(|| self.key.cmp(&other.key))
+    }
+}
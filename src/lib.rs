@@ -49,9 +49,56 @@ extern crate fpa;
 #[cfg(feature = "typenum")]
 extern crate typenum;
 
+#[cfg(feature = "approx")]
+extern crate approx;
+
 #[macro_use]
 mod assert;
 
+mod calibrate;
+
+mod dyn_eq;
+
+mod elementwise;
+
+mod full_scale;
+
+mod key;
+
+mod monotonic;
+
+mod near_miss;
+
+mod nearly;
+
+mod norm;
+
+mod ord;
+
+mod outliers;
+
+mod report;
+
+mod sequence;
+
+mod slice;
+
+mod soft;
+
+mod stats;
+
+mod tolerance;
+
+mod typed;
+
+mod unordered;
+
+mod up_to;
+
+mod zero;
+
+mod zero_padded;
+
 #[cfg(feature = "num-complex")]
 mod complex_impl;
 
@@ -66,17 +113,87 @@ mod ndarray_impl;
 #[cfg(feature = "fpa")]
 mod fpa_impl;
 
+#[cfg(feature = "float")]
+mod float_cmp;
+
+#[cfg(feature = "approx")]
+mod approx_impl;
+
 use std::rc::{Rc, Weak};
 
 use std::sync::Arc;
 
 use std::cell::{Cell, RefCell};
 
+pub use calibrate::{disable_calibration, enable_calibration, print_calibration, read_calibration,
+                    Calibration, CALIBRATE_ENV};
+pub use dyn_eq::{DynNearlyEq, TypeMismatch};
+pub use elementwise::{Elementwise, LengthMismatch};
+pub use full_scale::FullScale;
+pub use key::{NearlyKey, Quantized};
+pub use monotonic::{monotonic_violation, Monotonic};
+pub use near_miss::{disable_near_miss, enable_near_miss, near_misses, print_near_miss_summary,
+                    NearMiss, NEAR_MISS_ENV};
+pub use nearly::{DefaultDiff, Nearly, NearlyWith};
+pub use norm::{ByNorm, Magnitude, Norm};
+pub use ord::{nearly_sort_cmp, NearlyOrd};
+pub use outliers::Outliers;
+pub use report::{reporter_from_env, set_reporter, take_reporter, Comparison, JUnitReporter,
+                 JsonLinesReporter, Reporter, JSON_REPORT_ENV, JUNIT_REPORT_ENV, REPORT_PASSED_ENV};
+pub use sequence::Sequence;
+pub use slice::{NearlySliceExt, NearlyVecExt};
+pub use soft::{soft, SoftAsserts};
+pub use stats::ComparisonStats;
+pub use tolerance::{RequiredTolerance, Tolerance};
+pub use typed::{AbsTol, TolerancePolicy, Tolerant, Ulps};
+pub use unordered::Unordered;
+pub use up_to::{Factor, Scalable, UpTo};
+pub use zero::{NearlyZero, Target};
+pub use zero_padded::ZeroPadded;
+#[cfg(feature = "approx")]
+pub use approx_impl::{Approx, ApproxRelative, ApproxUlps};
+#[cfg(feature = "float")]
+pub use float_cmp::{FloatCmp, MaxUlps, Relative};
+
+#[doc(hidden)]
+pub use report::check as __check;
+#[doc(hidden)]
+pub use report::check_default as __check_default;
+#[doc(hidden)]
+pub use report::check_order as __check_order;
+#[doc(hidden)]
+pub use report::check_order_default as __check_order_default;
+#[doc(hidden)]
+pub use report::check_target as __check_target;
+#[doc(hidden)]
+pub use report::check_target_default as __check_target_default;
+#[doc(hidden)]
+pub use report::details as __details;
+#[doc(hidden)]
+pub use monotonic::check as __check_monotonic;
+#[doc(hidden)]
+pub use monotonic::check_default as __check_monotonic_default;
+
 /// Trait for nearly(approximately) equality comparisons.
+///
+/// Only `eq` is required. The default diff value used by the two-argument assertion macros
+/// comes from `DefaultTolerance`, which types without a sensible default need not implement.
 #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
 pub trait NearlyEq<Rhs: ?Sized = Self, Diff: ?Sized = Self> {
+    /// This method returns the default diff value of `DefaultTolerance`.
+    ///
+    /// Do not override it: the assertion macros call `DefaultTolerance::default_tolerance` directly.
+    /// Move the default diff value of an existing `eps` implementation into `DefaultTolerance` instead.
+    #[inline]
+    #[deprecated(since = "0.3.0", note = "implement `DefaultTolerance` and call `default_tolerance` instead")]
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-    fn eps() -> Diff;
+    fn eps() -> Diff
+    where
+        Self: DefaultTolerance<Diff> + Sized,
+        Diff: Sized,
+    {
+        Self::default_tolerance()
+    }
 
     /// This method tests for self and other values to be nearly(approximately) equal.
     #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
@@ -88,14 +205,187 @@ pub trait NearlyEq<Rhs: ?Sized = Self, Diff: ?Sized = Self> {
     fn ne(&self, other: &Rhs, eps: &Diff) -> bool {
         !self.eq(other, eps)
     }
+
+    /// This method returns the difference between self and other values as a ratio of the diff value.
+    ///
+    /// `0.0` means the values are equal, and values at or above `1.0` mean the diff value is used up.
+    /// Returns `None` if the difference cannot be measured.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn margin(&self, _other: &Rhs, _eps: &Diff) -> Option<f64> {
+        None
+    }
+
+    /// This method returns the difference between self and other values, independent of any diff value.
+    ///
+    /// Returns `None` if the difference cannot be measured.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn deviation(&self, _other: &Rhs) -> Option<Deviation> {
+        None
+    }
+
+    /// This method compares collections elementwise and returns summary statistics.
+    ///
+    /// Returns `None` for values that are not collections, or collections of different lengths.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn stats(&self, _other: &Rhs, _eps: &Diff) -> Option<ComparisonStats> {
+        None
+    }
+
+    /// This method describes the difference between self and other values for assertion messages.
+    ///
+    /// By default this is the `stats` of collections.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn explain(&self, other: &Rhs, eps: &Diff) -> Option<String> {
+        self.stats(other, eps).map(|stats| stats.to_string())
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl NearlyEq for f32 {
-    fn eps() -> f32 {
+/// Trait for types with a default diff value for nearly(approximately) equality comparisons.
+///
+/// The two-argument forms of the assertion macros use it when no diff value is given.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::{DefaultTolerance, NearlyEq};
+/// #[derive(Debug)]
+/// struct Meters(f64);
+///
+/// impl NearlyEq<Meters, f64> for Meters {
+///     fn eq(&self, other: &Meters, eps: &f64) -> bool {
+///         NearlyEq::eq(&self.0, &other.0, eps)
+///     }
+/// }
+///
+/// impl DefaultTolerance<f64> for Meters {
+///     fn default_tolerance() -> f64 {
+///         1e-3
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_nearly_eq!(Meters(1.0), Meters(1.0005)); // does not panic
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait DefaultTolerance<Diff = Self> {
+    /// This method returns the default diff value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn default_tolerance() -> Diff;
+}
+
+/// Differences between two values, as returned by `NearlyEq::deviation`.
+///
+/// For collections, each field holds the largest difference of any element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Deviation {
+    /// The absolute difference.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub abs: f64,
+    /// The absolute difference relative to the larger magnitude of the two values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub rel: f64,
+    /// The number of representable values between the two values, for floating and fixed point types.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub ulps: Option<u64>,
+}
+
+impl Deviation {
+    /// Returns a deviation with the largest of each difference.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn max(self, other: Deviation) -> Deviation {
+        Deviation {
+            abs: self.abs.max(other.abs),
+            rel: self.rel.max(other.rel),
+            ulps: match (self.ulps, other.ulps) {
+                (Some(x), Some(y)) => Some(x.max(y)),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn deviation_of(a: f64, b: f64, ulps: Option<u64>) -> Option<Deviation> {
+    if a == b {
+        Some(Deviation {
+            abs: 0.0,
+            rel: 0.0,
+            ulps: ulps.map(|_| 0),
+        })
+    } else if a.is_nan() || b.is_nan() {
+        None
+    } else {
+        let abs = (a - b).abs();
+        Some(Deviation {
+            abs,
+            rel: abs / a.abs().max(b.abs()),
+            ulps,
+        })
+    }
+}
+
+const NO_DEVIATION: Deviation = Deviation {
+    abs: 0.0,
+    rel: 0.0,
+    ulps: Some(0),
+};
+
+fn max_deviation<I: IntoIterator<Item = Option<Deviation>>>(deviations: I) -> Option<Deviation> {
+    deviations
+        .into_iter()
+        .try_fold(NO_DEVIATION, |max, deviation| deviation.map(|d| max.max(d)))
+}
+
+macro_rules! float_ulps {
+    ($name:ident, $T:ty, $I:ty) => {
+        fn $name(a: $T, b: $T) -> u64 {
+            fn ordered(x: $T) -> i128 {
+                let bits = x.to_bits() as $I;
+                if bits < 0 {
+                    -((bits & <$I>::MAX) as i128)
+                } else {
+                    bits as i128
+                }
+            }
+            (ordered(a) - ordered(b)).unsigned_abs() as u64
+        }
+    };
+}
+
+float_ulps!(f32_ulps, f32, i32);
+float_ulps!(f64_ulps, f64, i64);
+
+fn margin_ratio(diff: f64, eps: f64) -> Option<f64> {
+    if diff == 0.0 {
+        Some(0.0)
+    } else if diff.is_nan() || eps.is_nan() {
+        None
+    } else {
+        Some(diff / eps)
+    }
+}
+
+fn max_margin<I: IntoIterator<Item = Option<f64>>>(margins: I) -> Option<f64> {
+    margins
+        .into_iter()
+        .try_fold(0.0, |max: f64, margin| margin.map(|m| max.max(m)))
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl DefaultTolerance for f32 {
+    fn default_tolerance() -> f32 {
         1e-6
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl NearlyEq for f32 {
     fn eq(&self, other: &f32, eps: &f32) -> bool {
         let diff = (*self - *other).abs();
 
@@ -105,14 +395,29 @@ impl NearlyEq for f32 {
             diff < *eps
         }
     }
+
+    fn margin(&self, other: &f32, eps: &f32) -> Option<f64> {
+        if *self == *other {
+            Some(0.0)
+        } else {
+            margin_ratio((*self - *other).abs() as f64, *eps as f64)
+        }
+    }
+
+    fn deviation(&self, other: &f32) -> Option<Deviation> {
+        deviation_of(*self as f64, *other as f64, Some(f32_ulps(*self, *other)))
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl NearlyEq for f64 {
-    fn eps() -> f64 {
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl DefaultTolerance for f64 {
+    fn default_tolerance() -> f64 {
         1e-11
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl NearlyEq for f64 {
     fn eq(&self, other: &f64, eps: &f64) -> bool {
         let diff = (*self - *other).abs();
 
@@ -122,26 +427,49 @@ impl NearlyEq for f64 {
             diff < *eps
         }
     }
+
+    fn margin(&self, other: &f64, eps: &f64) -> Option<f64> {
+        if *self == *other {
+            Some(0.0)
+        } else {
+            margin_ratio((*self - *other).abs(), *eps)
+        }
+    }
+
+    fn deviation(&self, other: &f64) -> Option<Deviation> {
+        deviation_of(*self, *other, Some(f64_ulps(*self, *other)))
+    }
 }
 
 macro_rules! itype_impls {
     ($($T:ty)+) => {
         $(
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.0"))]
-            impl NearlyEq for $T {
-                fn eps() -> $T {
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl DefaultTolerance for $T {
+                fn default_tolerance() -> $T {
                     0
                 }
+            }
 
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.0"))]
+            impl NearlyEq for $T {
                 fn eq(&self, other: &$T, eps: &$T) -> bool {
-                    let diff = (*self - *other).abs();
+                    let diff = self.abs_diff(*other);
 
                     if *self == *other {
                         true
                     } else {
-                        diff < *eps
+                        *eps > 0 && diff < eps.unsigned_abs()
                     }
                 }
+
+                fn margin(&self, other: &$T, eps: &$T) -> Option<f64> {
+                    margin_ratio((*self as f64 - *other as f64).abs(), *eps as f64)
+                }
+
+                fn deviation(&self, other: &$T) -> Option<Deviation> {
+                    deviation_of(*self as f64, *other as f64, None)
+                }
             }
         )+
     }
@@ -155,12 +483,15 @@ itype_impls! { i128 }
 macro_rules! utype_impls {
     ($($T:ty)+) => {
         $(
-            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.0"))]
-            impl NearlyEq for $T {
-                fn eps() -> $T {
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl DefaultTolerance for $T {
+                fn default_tolerance() -> $T {
                     0
                 }
+            }
 
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.0"))]
+            impl NearlyEq for $T {
                 fn eq(&self, other: &$T, eps: &$T) -> bool {
                     let diff = if *self > *other { *self - *other } else { *other - *self };
 
@@ -170,6 +501,14 @@ macro_rules! utype_impls {
                         diff < *eps
                     }
                 }
+
+                fn margin(&self, other: &$T, eps: &$T) -> Option<f64> {
+                    margin_ratio((*self as f64 - *other as f64).abs(), *eps as f64)
+                }
+
+                fn deviation(&self, other: &$T) -> Option<Deviation> {
+                    deviation_of(*self as f64, *other as f64, None)
+                }
             }
         )+
     }
@@ -180,12 +519,15 @@ utype_impls! { u8 u16 u32 u64 }
 #[cfg(feature = "i128")]
 utype_impls! { u128 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<[A], B> for [C] {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for [C] {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<[A], B> for [C] {
     fn eq(&self, other: &[A], eps: &B) -> bool {
         if self.len() != other.len() {
             false
@@ -198,14 +540,41 @@ impl<A, B, C: NearlyEq<A, B>> NearlyEq<[A], B> for [C] {
             true
         }
     }
+
+    fn margin(&self, other: &[A], eps: &B) -> Option<f64> {
+        if self.len() != other.len() {
+            None
+        } else {
+            max_margin(self.iter().zip(other.iter()).map(|(x, y)| x.margin(y, eps)))
+        }
+    }
+
+    fn deviation(&self, other: &[A]) -> Option<Deviation> {
+        if self.len() != other.len() {
+            None
+        } else {
+            max_deviation(self.iter().zip(other.iter()).map(|(x, y)| x.deviation(y)))
+        }
+    }
+
+    fn stats(&self, other: &[A], eps: &B) -> Option<ComparisonStats> {
+        if self.len() != other.len() {
+            None
+        } else {
+            Some(ComparisonStats::from_pairs(self.iter().zip(other.iter()), eps))
+        }
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<Vec<A>, B> for Vec<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Vec<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<Vec<A>, B> for Vec<C> {
     fn eq(&self, other: &Vec<A>, eps: &B) -> bool {
         if self.len() != other.len() {
             false
@@ -218,39 +587,100 @@ impl<A, B, C: NearlyEq<A, B>> NearlyEq<Vec<A>, B> for Vec<C> {
             true
         }
     }
+
+    fn margin(&self, other: &Vec<A>, eps: &B) -> Option<f64> {
+        if self.len() != other.len() {
+            None
+        } else {
+            max_margin(self.iter().zip(other.iter()).map(|(x, y)| x.margin(y, eps)))
+        }
+    }
+
+    fn deviation(&self, other: &Vec<A>) -> Option<Deviation> {
+        if self.len() != other.len() {
+            None
+        } else {
+            max_deviation(self.iter().zip(other.iter()).map(|(x, y)| x.deviation(y)))
+        }
+    }
+
+    fn stats(&self, other: &Vec<A>, eps: &B) -> Option<ComparisonStats> {
+        if self.len() != other.len() {
+            None
+        } else {
+            Some(ComparisonStats::from_pairs(self.iter().zip(other.iter()), eps))
+        }
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
-impl<'a, A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<A, B> for &'a C {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B> + ?Sized> DefaultTolerance<B> for &C {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+#[allow(clippy::needless_lifetimes, clippy::needless_borrow)]
+impl<'a, A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<A, B> for &'a C {
     fn eq(&self, other: &A, eps: &B) -> bool {
         (**self).eq(&other, eps)
     }
+
+    fn margin(&self, other: &A, eps: &B) -> Option<f64> {
+        (**self).margin(other, eps)
+    }
+
+    fn deviation(&self, other: &A) -> Option<Deviation> {
+        (**self).deviation(other)
+    }
+
+    fn stats(&self, other: &A, eps: &B) -> Option<ComparisonStats> {
+        (**self).stats(other, eps)
+    }
+
+    fn explain(&self, other: &A, eps: &B) -> Option<String> {
+        (**self).explain(other, eps)
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.0"))]
-impl<'a, A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<A, B> for &'a mut C {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B> + ?Sized> DefaultTolerance<B> for &mut C {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.0"))]
+#[allow(clippy::needless_lifetimes, clippy::needless_borrow)]
+impl<'a, A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<A, B> for &'a mut C {
     fn eq(&self, other: &A, eps: &B) -> bool {
         (**self).eq(&other, eps)
     }
+
+    fn margin(&self, other: &A, eps: &B) -> Option<f64> {
+        (**self).margin(other, eps)
+    }
+
+    fn deviation(&self, other: &A) -> Option<Deviation> {
+        (**self).deviation(other)
+    }
+
+    fn stats(&self, other: &A, eps: &B) -> Option<ComparisonStats> {
+        (**self).stats(other, eps)
+    }
+
+    fn explain(&self, other: &A, eps: &B) -> Option<String> {
+        (**self).explain(other, eps)
+    }
 }
 
 macro_rules! array_impls {
     ($($N:expr)+) => {
         $(
             #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.1.0"))]
+            #[allow(clippy::reversed_empty_ranges)]
             impl<A, B, C: NearlyEq<A, B>> NearlyEq<[A; $N], B> for [C; $N] {
-                fn eps() -> B {
-                    C::eps()
-                }
-
                 fn eq(&self, other: &[A; $N], eps: &B) -> bool {
                     for i in 0..$N {
                         if self[i].ne(&other[i], eps) {
@@ -259,11 +689,30 @@ macro_rules! array_impls {
                     }
                     true
                 }
+
+                fn margin(&self, other: &[A; $N], eps: &B) -> Option<f64> {
+                    max_margin(self.iter().zip(other.iter()).map(|(x, y)| x.margin(y, eps)))
+                }
+
+                fn deviation(&self, other: &[A; $N]) -> Option<Deviation> {
+                    max_deviation(self.iter().zip(other.iter()).map(|(x, y)| x.deviation(y)))
+                }
+
+                fn stats(&self, other: &[A; $N], eps: &B) -> Option<ComparisonStats> {
+                    Some(ComparisonStats::from_pairs(self.iter().zip(other.iter()), eps))
+                }
             }
         )+
     }
 }
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>, const N: usize> DefaultTolerance<B> for [C; N] {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
+    }
+}
+
 array_impls! {
      0  1  2  3  4  5  6  7  8  9
     10 11 12 13 14 15 16 17 18 19
@@ -271,12 +720,16 @@ array_impls! {
     30 31 32
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.1"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<Option<A>, B> for Option<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Option<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.1"))]
+#[allow(clippy::needless_return, clippy::needless_borrowed_reference)]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<Option<A>, B> for Option<C> {
     fn eq(&self, other: &Option<A>, eps: &B) -> bool {
             match (self, other) {
                 (&None, &None) => return true,
@@ -284,59 +737,188 @@ impl<A, B, C: NearlyEq<A, B>> NearlyEq<Option<A>, B> for Option<C> {
                 (&Some(ref x), &Some(ref y)) => x.eq(y, eps),
             }
     }
+
+    fn margin(&self, other: &Option<A>, eps: &B) -> Option<f64> {
+        match (self, other) {
+            (None, None) => Some(0.0),
+            (None, _) | (_, None) => None,
+            (Some(x), Some(y)) => x.margin(y, eps),
+        }
+    }
+
+    fn deviation(&self, other: &Option<A>) -> Option<Deviation> {
+        match (self, other) {
+            (None, None) => Some(NO_DEVIATION),
+            (None, _) | (_, None) => None,
+            (Some(x), Some(y)) => x.deviation(y),
+        }
+    }
+
+    fn stats(&self, other: &Option<A>, eps: &B) -> Option<ComparisonStats> {
+        match (self, other) {
+            (Some(x), Some(y)) => x.stats(y, eps),
+            _ => None,
+        }
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<Rc<A>, B> for Rc<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B> + ?Sized> DefaultTolerance<B> for Box<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<Box<A>, B> for Box<C> {
+    fn eq(&self, other: &Box<A>, eps: &B) -> bool {
+        self.as_ref().eq(other, eps)
+    }
+
+    fn margin(&self, other: &Box<A>, eps: &B) -> Option<f64> {
+        self.as_ref().margin(other, eps)
+    }
+
+    fn deviation(&self, other: &Box<A>) -> Option<Deviation> {
+        self.as_ref().deviation(other)
+    }
+
+    fn stats(&self, other: &Box<A>, eps: &B) -> Option<ComparisonStats> {
+        self.as_ref().stats(other, eps)
     }
 
+    fn explain(&self, other: &Box<A>, eps: &B) -> Option<String> {
+        self.as_ref().explain(other, eps)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Rc<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<Rc<A>, B> for Rc<C> {
     fn eq(&self, other: &Rc<A>, eps: &B) -> bool {
         self.as_ref().eq(other, eps)
     }
+
+    fn margin(&self, other: &Rc<A>, eps: &B) -> Option<f64> {
+        self.as_ref().margin(other, eps)
+    }
+
+    fn deviation(&self, other: &Rc<A>) -> Option<Deviation> {
+        self.as_ref().deviation(other)
+    }
+
+    fn stats(&self, other: &Rc<A>, eps: &B) -> Option<ComparisonStats> {
+        self.as_ref().stats(other, eps)
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<Arc<A>, B> for Arc<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Arc<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<Arc<A>, B> for Arc<C> {
     fn eq(&self, other: &Arc<A>, eps: &B) -> bool {
         self.as_ref().eq(other, eps)
     }
+
+    fn margin(&self, other: &Arc<A>, eps: &B) -> Option<f64> {
+        self.as_ref().margin(other, eps)
+    }
+
+    fn deviation(&self, other: &Arc<A>) -> Option<Deviation> {
+        self.as_ref().deviation(other)
+    }
+
+    fn stats(&self, other: &Arc<A>, eps: &B) -> Option<ComparisonStats> {
+        self.as_ref().stats(other, eps)
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
-impl<A, B, C: NearlyEq<A, B>> NearlyEq<Weak<A>, B> for Weak<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Weak<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
+impl<A, B, C: NearlyEq<A, B>> NearlyEq<Weak<A>, B> for Weak<C> {
     fn eq(&self, other: &Weak<A>, eps: &B) -> bool {
         self.upgrade().eq(&other.upgrade(), eps)
     }
+
+    fn margin(&self, other: &Weak<A>, eps: &B) -> Option<f64> {
+        self.upgrade().margin(&other.upgrade(), eps)
+    }
+
+    fn deviation(&self, other: &Weak<A>) -> Option<Deviation> {
+        self.upgrade().deviation(&other.upgrade())
+    }
+
+    fn stats(&self, other: &Weak<A>, eps: &B) -> Option<ComparisonStats> {
+        self.upgrade().stats(&other.upgrade(), eps)
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
-impl<A: Copy + ?Sized, B, C: NearlyEq<A, B> +  Copy + ?Sized> NearlyEq<Cell<A>, B> for Cell<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B>> DefaultTolerance<B> for Cell<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
+#[allow(clippy::needless_maybe_sized)]
+impl<A: Copy + ?Sized, B, C: NearlyEq<A, B> +  Copy + ?Sized> NearlyEq<Cell<A>, B> for Cell<C> {
     fn eq(&self, other: &Cell<A>, eps: &B) -> bool {
         (*self).get().eq(&(*other).get(), eps)
     }
+
+    fn margin(&self, other: &Cell<A>, eps: &B) -> Option<f64> {
+        (*self).get().margin(&(*other).get(), eps)
+    }
+
+    fn deviation(&self, other: &Cell<A>) -> Option<Deviation> {
+        (*self).get().deviation(&(*other).get())
+    }
+
+    fn stats(&self, other: &Cell<A>, eps: &B) -> Option<ComparisonStats> {
+        (*self).get().stats(&(*other).get(), eps)
+    }
 }
 
-#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
-impl<A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<RefCell<A>, B> for RefCell<C> {
-    fn eps() -> B {
-        C::eps()
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: DefaultTolerance<B> + ?Sized> DefaultTolerance<B> for RefCell<C> {
+    fn default_tolerance() -> B {
+        C::default_tolerance()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.2.2"))]
+impl<A: ?Sized, B, C: NearlyEq<A, B> + ?Sized> NearlyEq<RefCell<A>, B> for RefCell<C> {
     fn eq(&self, other: &RefCell<A>, eps: &B) -> bool {
         (*self).borrow().eq(&(*other).borrow(), eps)
     }
+
+    fn margin(&self, other: &RefCell<A>, eps: &B) -> Option<f64> {
+        (*self).borrow().margin(&(*other).borrow(), eps)
+    }
+
+    fn deviation(&self, other: &RefCell<A>) -> Option<Deviation> {
+        (*self).borrow().deviation(&(*other).borrow())
+    }
+
+    fn stats(&self, other: &RefCell<A>, eps: &B) -> Option<ComparisonStats> {
+        (*self).borrow().stats(&(*other).borrow(), eps)
+    }
 }
@@ -0,0 +1,178 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+use std::panic::Location;
+
+use report;
+use {max_deviation, max_margin, NearlyEq, Sequence};
+
+/// The orders checked by `assert_nearly_monotonic!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub enum Monotonic {
+    /// Each element is greater than or nearly equal to the previous one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Increasing,
+    /// Each element is greater than and not nearly equal to the previous one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    StrictlyIncreasing,
+    /// Each element is less than or nearly equal to the previous one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Decreasing,
+    /// Each element is less than and not nearly equal to the previous one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    StrictlyDecreasing,
+}
+
+impl Monotonic {
+    fn holds<T: NearlyEq<T, B> + PartialOrd, B>(self, a: &T, b: &T, eps: &B) -> bool {
+        match self {
+            Monotonic::Increasing => a <= b || NearlyEq::eq(a, b, eps),
+            Monotonic::StrictlyIncreasing => a < b && NearlyEq::ne(a, b, eps),
+            Monotonic::Decreasing => a >= b || NearlyEq::eq(a, b, eps),
+            Monotonic::StrictlyDecreasing => a > b && NearlyEq::ne(a, b, eps),
+        }
+    }
+
+    fn assertion(self) -> &'static str {
+        match self {
+            Monotonic::Increasing => "values are nearly increasing",
+            Monotonic::StrictlyIncreasing => "values are nearly strictly increasing",
+            Monotonic::Decreasing => "values are nearly decreasing",
+            Monotonic::StrictlyDecreasing => "values are nearly strictly decreasing",
+        }
+    }
+}
+
+impl fmt::Display for Monotonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Monotonic::Increasing => "increasing",
+            Monotonic::StrictlyIncreasing => "strictly increasing",
+            Monotonic::Decreasing => "decreasing",
+            Monotonic::StrictlyDecreasing => "strictly decreasing",
+        })
+    }
+}
+
+/// Returns the indices of the first pair of elements that breaks the order,
+/// or `None` if the values are nearly(approximately) monotonic.
+///
+/// The pair is the one `assert_nearly_monotonic!` reports: the earlier element the order was
+/// checked against, and the first element breaking the order.
+///
+/// Each element is compared with the largest of the elements before it, or the smallest for
+/// the decreasing orders, so many small inversions cannot add up to more than the diff value.
+/// Inversions within the diff value are forgiven by the non-strict orders, and
+/// steps within the diff value break the strict orders.
+///
+/// # Examples
+///
+/// ```rust
+/// # use nearly_eq::{monotonic_violation, Monotonic};
+/// let cdf = [0.1f64, 0.5, 0.5 - 1e-13, 1.0];
+/// assert_eq!(monotonic_violation(&cdf, &1e-12, Monotonic::Increasing), None);
+/// assert_eq!(monotonic_violation(&cdf, &1e-12, Monotonic::StrictlyIncreasing), Some((1, 2)));
+///
+/// let drift = [1f64, 1.0 - 1e-13, 1.0 - 2e-13];
+/// assert_eq!(monotonic_violation(&drift, &1.5e-13, Monotonic::Increasing), Some((0, 2)));
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn monotonic_violation<S, B>(values: &S, eps: &B, order: Monotonic) -> Option<(usize, usize)>
+where
+    S: Sequence + ?Sized,
+    S::Item: NearlyEq<S::Item, B> + PartialOrd,
+{
+    violation(values, eps, order, |_, _| ())
+}
+
+// Returns the indices of the running extreme and of the first element breaking the order with it.
+// `visit` is called with the running extreme and each element not beyond it, up to the violation.
+fn violation<S, B, F>(values: &S, eps: &B, order: Monotonic, mut visit: F) -> Option<(usize, usize)>
+where
+    S: Sequence + ?Sized,
+    S::Item: NearlyEq<S::Item, B> + PartialOrd,
+    F: FnMut(&S::Item, &S::Item),
+{
+    let mut elements = values.elements().enumerate();
+    let (mut peak, mut extreme) = elements.next()?;
+    for (i, element) in elements {
+        let holds = order.holds(extreme, element, eps);
+        let beyond = match order {
+            Monotonic::Increasing | Monotonic::StrictlyIncreasing => element > extreme,
+            Monotonic::Decreasing | Monotonic::StrictlyDecreasing => element < extreme,
+        };
+        if !holds || !beyond {
+            visit(extreme, element);
+        }
+        if !holds {
+            return Some((peak, i));
+        }
+        if beyond {
+            peak = i;
+            extreme = element;
+        }
+    }
+    None
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check<S, B>(values: &S, eps: &B, order: Monotonic) -> Option<String>
+where
+    S: Sequence + fmt::Debug + ?Sized,
+    S::Item: NearlyEq<S::Item, B> + PartialOrd + fmt::Debug,
+    B: fmt::Debug,
+{
+    observe(values, eps, order, || format!("{:?}", eps))
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_default<S, B>(values: &S, eps: &B, order: Monotonic) -> Option<String>
+where
+    S: Sequence + fmt::Debug + ?Sized,
+    S::Item: NearlyEq<S::Item, B> + PartialOrd + fmt::Debug,
+{
+    observe(values, eps, order, || "default".to_string())
+}
+
+// The hooks see the largest difference forgiven between an element and the running extreme,
+// or the difference of the violating pair.
+#[track_caller]
+fn observe<S, B, F>(values: &S, eps: &B, order: Monotonic, eps_text: F) -> Option<String>
+where
+    S: Sequence + fmt::Debug + ?Sized,
+    S::Item: NearlyEq<S::Item, B> + PartialOrd + fmt::Debug,
+    F: FnOnce() -> String,
+{
+    let found = violation(values, eps, order, |_, _| ());
+    report::observe(
+        found.is_none(),
+        order.assertion(),
+        Location::caller(),
+        || {
+            let mut margins = Vec::new();
+            violation(values, eps, order, |x, y| margins.push(x.margin(y, eps)));
+            max_margin(margins)
+        },
+        || {
+            let mut deviations = Vec::new();
+            violation(values, eps, order, |x, y| deviations.push(x.deviation(y)));
+            max_deviation(deviations)
+        },
+        || (format!("{:?}", values), order.to_string(), eps_text()),
+    );
+    let (peak, i) = found?;
+    Some(format!(
+        "elements at index {} and {} (`{:?}` and `{:?}`) are not nearly {}",
+        peak,
+        i,
+        values.elements().nth(peak)?,
+        values.elements().nth(i)?,
+        order
+    ))
+}
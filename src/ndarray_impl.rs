@@ -4,17 +4,25 @@
 //! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
 
 use ndarray::{ArrayBase, Axis, Data, Dimension};
-use NearlyEq;
+use tolerance::max_tolerance;
+use {max_deviation, max_margin, ComparisonStats, DefaultDiff, DefaultTolerance, Deviation, NearlyEq, NearlyZero,
+     RequiredTolerance, Sequence, Target, Tolerance};
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.3.0"))]
+impl<B, C: Data, D: Dimension> DefaultTolerance<B> for ArrayBase<C, D>
+where
+    C::Elem: DefaultTolerance<B>,
+{
+    fn default_tolerance() -> B {
+        C::Elem::default_tolerance()
+    }
+}
 
 #[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.2.0"))]
 impl<A: Data, B, C: Data, D: Dimension> NearlyEq<ArrayBase<A, D>, B> for ArrayBase<C, D>
 where
     C::Elem: NearlyEq<A::Elem, B> + Sized,
 {
-    fn eps() -> B {
-        C::Elem::eps()
-    }
-
     fn eq(&self, other: &ArrayBase<A, D>, eps: &B) -> bool {
         if self.ndim() != other.ndim() {
             return false;
@@ -38,4 +46,89 @@ where
             }
         }
     }
+
+    fn margin(&self, other: &ArrayBase<A, D>, eps: &B) -> Option<f64> {
+        if self.shape() != other.shape() {
+            None
+        } else {
+            max_margin(self.iter().zip(other.iter()).map(|(x, y)| x.margin(y, eps)))
+        }
+    }
+
+    fn deviation(&self, other: &ArrayBase<A, D>) -> Option<Deviation> {
+        if self.shape() != other.shape() {
+            None
+        } else {
+            max_deviation(self.iter().zip(other.iter()).map(|(x, y)| x.deviation(y)))
+        }
+    }
+
+    fn stats(&self, other: &ArrayBase<A, D>, eps: &B) -> Option<ComparisonStats> {
+        if self.shape() != other.shape() {
+            None
+        } else {
+            Some(ComparisonStats::from_pairs(self.iter().zip(other.iter()), eps))
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.3.0"))]
+impl<A: Data, B: PartialOrd, C: Data, D: Dimension> RequiredTolerance<ArrayBase<A, D>, B> for ArrayBase<C, D>
+where
+    C::Elem: RequiredTolerance<A::Elem, B> + Sized,
+{
+    fn required_tolerance(&self, other: &ArrayBase<A, D>) -> Tolerance<B> {
+        if self.shape() != other.shape() {
+            Tolerance::Never
+        } else {
+            max_tolerance(self.iter().zip(other.iter()).map(|(x, y)| x.required_tolerance(y)))
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.3.0"))]
+impl<S: Data, D: Dimension> Sequence for ArrayBase<S, D> {
+    type Item = S::Elem;
+
+    fn len(&self) -> usize {
+        ArrayBase::len(self)
+    }
+
+    fn elements<'a>(&'a self) -> Box<dyn Iterator<Item = &'a S::Elem> + 'a> {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.3.0"))]
+impl<B, C: Data, D: Dimension> NearlyZero<B> for ArrayBase<C, D>
+where
+    C::Elem: NearlyZero<B> + Sized,
+{
+    fn is_nearly_zero(&self, eps: &B) -> bool {
+        self.iter().all(|x| x.is_nearly_zero(eps))
+    }
+
+    fn is_nearly_integer(&self, eps: &B) -> bool {
+        self.iter().all(|x| x.is_nearly_integer(eps))
+    }
+
+    fn is_nearly_one(&self, eps: &B) -> bool {
+        self.iter().all(|x| x.is_nearly_one(eps))
+    }
+
+    fn margin_from(&self, target: Target, eps: &B) -> Option<f64> {
+        max_margin(self.iter().map(|x| x.margin_from(target, eps)))
+    }
+
+    fn deviation_from(&self, target: Target) -> Option<Deviation> {
+        max_deviation(self.iter().map(|x| x.deviation_from(target)))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "ndarray", since = "0.3.0"))]
+impl<C: Data, D: Dimension> DefaultDiff for ArrayBase<C, D>
+where
+    C::Elem: DefaultDiff + Sized,
+{
+    type Diff = <C::Elem as DefaultDiff>::Diff;
 }
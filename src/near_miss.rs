@@ -0,0 +1,127 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::collections::BTreeMap;
+use std::env;
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, Once};
+
+/// Environment variable enabling near-miss collection, holding the threshold fraction (e.g. `0.9`).
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub const NEAR_MISS_ENV: &str = "NEARLY_EQ_NEAR_MISS";
+
+const DEFAULT_THRESHOLD: f64 = 0.9;
+
+/// A call site whose passing comparisons used more than the threshold fraction of their diff value.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct NearMiss {
+    /// The location of the assertion.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub location: &'static Location<'static>,
+    /// The number of near-miss comparisons at this location.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub count: usize,
+    /// The largest observed margin, see `NearlyEq::margin`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub max_margin: f64,
+}
+
+struct State {
+    threshold: f64,
+    sites: BTreeMap<(&'static str, u32, u32), NearMiss>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STATE: Mutex<State> = Mutex::new(State {
+    threshold: DEFAULT_THRESHOLD,
+    sites: BTreeMap::new(),
+});
+static FROM_ENV: Once = Once::new();
+
+/// Starts collecting passing comparisons whose margin is at least `threshold` (e.g. `0.9` for 90%).
+///
+/// Read them with `near_misses`, or print them with `print_near_miss_summary`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn enable_near_miss(threshold: f64) {
+    STATE.lock().unwrap_or_else(|e| e.into_inner()).threshold = threshold;
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Stops collecting near misses. Already collected ones are kept.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn disable_near_miss() {
+    ENABLED.store(false, Ordering::SeqCst);
+}
+
+/// Returns the collected near misses, riskiest call site first.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn near_misses() -> Vec<NearMiss> {
+    let state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut sites: Vec<NearMiss> = state.sites.values().cloned().collect();
+    sites.sort_by(|a, b| b.max_margin.total_cmp(&a.max_margin));
+    sites
+}
+
+/// Prints the collected near misses to stderr, riskiest call site first.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn print_near_miss_summary() {
+    let sites = near_misses();
+    if sites.is_empty() {
+        return;
+    }
+    let threshold = STATE.lock().unwrap_or_else(|e| e.into_inner()).threshold;
+    eprintln!(
+        "nearly_eq: {} call site(s) used at least {}% of their tolerance:",
+        sites.len(),
+        threshold * 100.0
+    );
+    for site in sites {
+        eprintln!(
+            "    {}: {:.1}% ({} comparison(s))",
+            site.location,
+            site.max_margin * 100.0,
+            site.count
+        );
+    }
+}
+
+pub(crate) fn init_from_env() {
+    FROM_ENV.call_once(|| {
+        if let Some(value) = env::var_os(NEAR_MISS_ENV) {
+            let threshold = value
+                .to_str()
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(DEFAULT_THRESHOLD);
+            enable_near_miss(threshold);
+        }
+    });
+}
+
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn observe(margin: Option<f64>, location: &'static Location<'static>) {
+    let margin = match margin {
+        Some(margin) => margin,
+        None => return,
+    };
+    let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    if margin < state.threshold {
+        return;
+    }
+    let site = state
+        .sites
+        .entry((location.file(), location.line(), location.column()))
+        .or_insert(NearMiss {
+            location,
+            count: 0,
+            max_margin: margin,
+        });
+    site.count += 1;
+    site.max_margin = site.max_margin.max(margin);
+}
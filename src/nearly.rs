@@ -0,0 +1,236 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+use {DefaultTolerance, NearlyEq, Tolerant};
+
+/// Trait for types with a default diff value type, the one `Nearly` takes its `DefaultTolerance` from.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait DefaultDiff {
+    /// The default diff value type.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    type Diff;
+}
+
+macro_rules! impls {
+    ($($T:ty)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl DefaultDiff for $T {
+                type Diff = $T;
+            }
+        )+
+    }
+}
+
+impls! { f32 f64 i8 i16 i32 i64 u8 u16 u32 u64 }
+
+#[cfg(feature = "i128")]
+impls! { i128 u128 }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for [C] {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Vec<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff, const N: usize> DefaultDiff for [C; N] {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff + ?Sized> DefaultDiff for &C {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff + ?Sized> DefaultDiff for &mut C {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Option<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Rc<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Arc<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Weak<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff> DefaultDiff for Cell<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<C: DefaultDiff + ?Sized> DefaultDiff for RefCell<C> {
+    type Diff = C::Diff;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P> DefaultDiff for Tolerant<T, P> {
+    type Diff = P;
+}
+
+/// Wrapper whose `PartialEq` is nearly(approximately) equality with the default diff value.
+///
+/// This makes `NearlyEq` values usable with `assert_eq!`, `Vec::contains`
+/// and derived `PartialEq` implementations.
+///
+/// # Examples
+///
+/// ```rust
+/// # use nearly_eq::Nearly;
+/// #[derive(Debug, PartialEq)]
+/// struct Sample {
+///     name: &'static str,
+///     value: Nearly<f64>,
+/// }
+///
+/// assert_eq!(
+///     Sample { name: "a", value: Nearly(0.1 + 0.2) },
+///     Sample { name: "a", value: Nearly(0.3) }
+/// );
+/// assert!(vec![Nearly(1f64), Nearly(0.3)].contains(&Nearly(0.1 + 0.2)));
+///
+/// let (left, right) = (vec![0.1 + 0.2, 1.0], vec![0.3f64, 1.0]);
+/// assert_eq!(Nearly::from_ref(&left[..]), Nearly::from_ref(&right[..])); // does not copy
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Nearly<T: ?Sized>(#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))] pub T);
+
+impl<T> Nearly<T> {
+    /// Returns the wrapped value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: ?Sized> Nearly<T> {
+    /// Wraps a borrowed value without copying it.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn from_ref(value: &T) -> &Nearly<T> {
+        // Nearly<T> is a transparent wrapper, so it has the same layout as T.
+        unsafe { &*(value as *const T as *const Nearly<T>) }
+    }
+}
+
+impl<T: ?Sized> Deref for Nearly<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Nearly<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, U> PartialEq<Nearly<U>> for Nearly<T>
+where
+    T: DefaultDiff + NearlyEq<U, <T as DefaultDiff>::Diff> + DefaultTolerance<<T as DefaultDiff>::Diff> + ?Sized,
+    U: ?Sized,
+{
+    fn eq(&self, other: &Nearly<U>) -> bool {
+        NearlyEq::eq(&self.0, &other.0, &T::default_tolerance())
+    }
+}
+
+/// Wrapper whose `PartialEq` is nearly(approximately) equality with an embedded diff value.
+///
+/// The smaller of the two diff values is used, so the comparison is symmetric.
+/// Values with incomparable diff values, such as `NaN`, are never equal.
+///
+/// # Examples
+///
+/// ```rust
+/// # use nearly_eq::NearlyWith;
+/// assert_eq!(NearlyWith::new(1f64, 0.2), NearlyWith::new(1.1f64, 0.2));
+/// assert_ne!(NearlyWith::new(1f64, 0.01), NearlyWith::new(1.1f64, 0.01));
+/// assert_eq!(NearlyWith::new(vec![1f64, 2.0], 0.2), NearlyWith::new(vec![1.1f64, 2.1], 0.2));
+/// ```
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct NearlyWith<T, Tol> {
+    /// The wrapped value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub value: T,
+    /// The diff value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub eps: Tol,
+}
+
+impl<T, Tol> NearlyWith<T, Tol> {
+    /// Wraps the value with the diff value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(value: T, eps: Tol) -> NearlyWith<T, Tol> {
+        NearlyWith { value, eps }
+    }
+
+    /// Returns the wrapped value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, Tol> Deref for NearlyWith<T, Tol> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Debug, Tol> fmt::Debug for NearlyWith<T, Tol> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, U, Tol> PartialEq<NearlyWith<U, Tol>> for NearlyWith<T, Tol>
+where
+    T: NearlyEq<U, Tol>,
+    Tol: PartialOrd,
+{
+    fn eq(&self, other: &NearlyWith<U, Tol>) -> bool {
+        let eps = match self.eps.partial_cmp(&other.eps) {
+            Some(Ordering::Greater) => &other.eps,
+            Some(_) => &self.eps,
+            None => return false,
+        };
+        NearlyEq::eq(&self.value, &other.value, eps)
+    }
+}
@@ -0,0 +1,216 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use {margin_ratio, DefaultTolerance, NearlyEq, Sequence};
+
+/// Trait for element types with a magnitude, used by the norm-based comparisons.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait Magnitude {
+    /// This method returns the absolute value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn magnitude(&self) -> f64;
+
+    /// This method returns the absolute value of the difference between self and other values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn distance(&self, other: &Self) -> f64;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl Magnitude for f32 {
+    fn magnitude(&self) -> f64 {
+        self.abs() as f64
+    }
+
+    fn distance(&self, other: &f32) -> f64 {
+        (*self as f64 - *other as f64).abs()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl Magnitude for f64 {
+    fn magnitude(&self) -> f64 {
+        self.abs()
+    }
+
+    fn distance(&self, other: &f64) -> f64 {
+        (*self - *other).abs()
+    }
+}
+
+/// The vector norms used by `ByNorm`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub enum Norm {
+    /// The sum of the magnitudes.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    L1,
+    /// The square root of the sum of the squared magnitudes, the Euclidean norm.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    L2,
+    /// The largest magnitude.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    LInf,
+}
+
+impl Norm {
+    /// Returns the norm of the given magnitudes, `NaN` if any of them is `NaN`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn of<I: IntoIterator<Item = f64>>(self, magnitudes: I) -> f64 {
+        let magnitudes = magnitudes.into_iter();
+        match self {
+            Norm::L1 => magnitudes.sum(),
+            Norm::L2 => magnitudes.map(|m| m * m).sum::<f64>().sqrt(),
+            Norm::LInf => magnitudes.fold(0.0, |max, m| if m > max || m.is_nan() { m } else { max }),
+        }
+    }
+}
+
+/// Compares collections as vectors, by the norm of their difference.
+///
+/// The values are nearly equal if `||left - right|| <= eps + rtol * ||right||`,
+/// where `eps` is the absolute diff value and `rtol` defaults to zero.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::ByNorm;
+/// # fn main() {
+/// let point = [1f64, 2.0, 2.0];
+/// assert_nearly_eq!(ByNorm::l2(&point), [1.001f64, 2.0, 2.0], 1e-2); // does not panic
+/// assert_nearly_eq!(ByNorm::l2(&point).rtol(1e-3), [1.001f64, 2.0, 2.0], 0.0); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::ByNorm;
+/// # fn main() {
+/// let point = [1f64, 2.0, 2.0];
+/// assert_nearly_eq!(ByNorm::l1(&point), [1.006f64, 2.006, 2.0], 1e-2); // panics
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct ByNorm<'a, L: ?Sized + 'a> {
+    values: &'a L,
+    norm: Norm,
+    rtol: f64,
+}
+
+impl<'a, L: Sequence + ?Sized> ByNorm<'a, L>
+where
+    L::Item: Magnitude,
+{
+    /// Compares by the given norm.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(values: &'a L, norm: Norm) -> ByNorm<'a, L> {
+        ByNorm {
+            values,
+            norm,
+            rtol: 0.0,
+        }
+    }
+
+    /// Compares by the L1 norm.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn l1(values: &'a L) -> ByNorm<'a, L> {
+        ByNorm::new(values, Norm::L1)
+    }
+
+    /// Compares by the L2 norm, the Euclidean distance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn l2(values: &'a L) -> ByNorm<'a, L> {
+        ByNorm::new(values, Norm::L2)
+    }
+
+    /// Compares by the L-infinity norm.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn linf(values: &'a L) -> ByNorm<'a, L> {
+        ByNorm::new(values, Norm::LInf)
+    }
+
+    /// Sets the tolerance relative to the norm of the right values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn rtol(mut self, rtol: f64) -> ByNorm<'a, L> {
+        self.rtol = rtol;
+        self
+    }
+
+    /// Returns the norm of the difference, or `None` if the lengths differ.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn distance<R: Sequence<Item = L::Item> + ?Sized>(&self, other: &R) -> Option<f64> {
+        if self.values.len() != other.len() {
+            None
+        } else {
+            Some(self.norm.of(
+                self.values
+                    .elements()
+                    .zip(other.elements())
+                    .map(|(x, y)| x.distance(y)),
+            ))
+        }
+    }
+
+    fn bound<R: Sequence<Item = L::Item> + ?Sized>(&self, other: &R, eps: f64) -> f64 {
+        if self.rtol == 0.0 {
+            eps
+        } else {
+            eps + self.rtol * self.norm.of(other.elements().map(Magnitude::magnitude))
+        }
+    }
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for ByNorm<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, L, R> NearlyEq<R, f64> for ByNorm<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence<Item = L::Item> + ?Sized,
+    L::Item: Magnitude,
+{
+    fn eq(&self, other: &R, eps: &f64) -> bool {
+        match self.distance(other) {
+            Some(distance) => distance <= self.bound(other, *eps),
+            None => false,
+        }
+    }
+
+    fn margin(&self, other: &R, eps: &f64) -> Option<f64> {
+        margin_ratio(self.distance(other)?, self.bound(other, *eps))
+    }
+
+    fn explain(&self, other: &R, eps: &f64) -> Option<String> {
+        let distance = match self.distance(other) {
+            Some(distance) => distance,
+            None => {
+                return Some(format!(
+                    "lengths differ: {} and {}",
+                    self.values.len(),
+                    other.len()
+                ))
+            }
+        };
+        Some(format!(
+            "{:?} norm of the difference {:e}, allowed {:e}",
+            self.norm,
+            distance,
+            self.bound(other, *eps)
+        ))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, L: Sequence + ?Sized> DefaultTolerance<f64> for ByNorm<'a, L> {
+    fn default_tolerance() -> f64 {
+        f64::default_tolerance()
+    }
+}
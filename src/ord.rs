@@ -0,0 +1,103 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::cmp::Ordering;
+
+use {NearlyEq, NearlyKey};
+
+/// Trait for nearly(approximately) ordering comparisons.
+///
+/// Values that are nearly equal compare as `Ordering::Equal`, and otherwise
+/// by their `PartialOrd` implementation.
+///
+/// Near equality is not transitive, so `nearly_cmp` is not a total order and must not be
+/// used as a sort comparator. Sort with `nearly_sort_cmp` instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::cmp::Ordering;
+/// # use nearly_eq::NearlyOrd;
+/// assert!(1f64.nearly_le(&(1.0 - 1e-13), &1e-12));
+/// assert_eq!(1f64.nearly_cmp(&(1.0 - 1e-13), &1e-12), Some(Ordering::Equal));
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait NearlyOrd<Rhs: ?Sized = Self, Diff = Self>: NearlyEq<Rhs, Diff> + PartialOrd<Rhs> {
+    /// This method returns an ordering between self and other values, `Ordering::Equal` if they are nearly equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_cmp(&self, other: &Rhs, eps: &Diff) -> Option<Ordering> {
+        if NearlyEq::eq(self, other, eps) {
+            Some(Ordering::Equal)
+        } else {
+            self.partial_cmp(other)
+        }
+    }
+
+    /// This method tests less than and not nearly equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_lt(&self, other: &Rhs, eps: &Diff) -> bool {
+        self.nearly_cmp(other, eps) == Some(Ordering::Less)
+    }
+
+    /// This method tests less than or nearly equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_le(&self, other: &Rhs, eps: &Diff) -> bool {
+        matches!(self.nearly_cmp(other, eps), Some(Ordering::Less) | Some(Ordering::Equal))
+    }
+
+    /// This method tests greater than and not nearly equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_gt(&self, other: &Rhs, eps: &Diff) -> bool {
+        self.nearly_cmp(other, eps) == Some(Ordering::Greater)
+    }
+
+    /// This method tests greater than or nearly equal.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_ge(&self, other: &Rhs, eps: &Diff) -> bool {
+        matches!(self.nearly_cmp(other, eps), Some(Ordering::Greater) | Some(Ordering::Equal))
+    }
+}
+
+macro_rules! impls {
+    ($($T:ty)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl NearlyOrd for $T {}
+        )+
+    }
+}
+
+impls! { f32 f64 i8 i16 i32 i64 u8 u16 u32 u64 }
+
+#[cfg(feature = "i128")]
+impls! { i128 u128 }
+
+/// Compares values for sorting, treating values in the same cell of size `eps` as `Ordering::Equal`.
+///
+/// The values are snapped to cells with `NearlyKey` and ordered by cell, which is a total order,
+/// so unlike `nearly_cmp` this is safe to use as a sort comparator. Values closer than `eps`
+/// compare `Equal` if they share a cell, and keep their order across a cell boundary.
+/// A stable sort keeps the input order of equal values. `NaN` sorts first.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::cmp::Ordering;
+/// # use nearly_eq::nearly_sort_cmp;
+/// let mut values = vec![2f64, 1.25, 1.0, f64::NAN];
+/// values.sort_by(|a, b| nearly_sort_cmp(a, b, 0.5));
+/// assert!(values[0].is_nan());
+/// assert_eq!(values[1..], [1.25, 1.0, 2.0]);
+/// assert_eq!(nearly_sort_cmp(&0.9f64, &1.0, 0.5), Ordering::Less);
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn nearly_sort_cmp<T: NearlyKey + ?Sized>(a: &T, b: &T, eps: f64) -> Ordering {
+    a.key(eps).cmp(&b.key(eps))
+}
@@ -0,0 +1,216 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use {ComparisonStats, DefaultTolerance, NearlyEq, Sequence};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mode {
+    Count(usize),
+    Percent(f64),
+    Percentile(f64),
+}
+
+/// Compares collections elementwise, allowing some elements to be outside the diff value.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Outliers;
+/// # fn main() {
+/// let left = vec![1f64, 2.0, 3.5, 4.0];
+/// let right = vec![1f64, 2.0, 3.0, 4.0];
+/// assert_nearly_eq!(Outliers::at_most(&left, 1), right, 1e-6); // does not panic
+/// assert_nearly_eq!(Outliers::at_most_percent(&left, 25.0), right, 1e-6); // does not panic
+/// assert_nearly_eq!(Outliers::percentile(&left, 75.0), right, 1e-6); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Outliers;
+/// # fn main() {
+/// let left = vec![1f64, 2.5, 3.5, 4.0];
+/// let right = vec![1f64, 2.0, 3.0, 4.0];
+/// assert_nearly_eq!(Outliers::at_most(&left, 1), right, 1e-6); // panics, reporting indices 1 and 2
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Outliers<'a, L: ?Sized + 'a> {
+    values: &'a L,
+    mode: Mode,
+}
+
+impl<'a, L: Sequence + ?Sized> Outliers<'a, L> {
+    /// Passes if at most `count` elements are not nearly equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn at_most(values: &'a L, count: usize) -> Outliers<'a, L> {
+        Outliers {
+            values,
+            mode: Mode::Count(count),
+        }
+    }
+
+    /// Passes if at most `percent` percent of the elements are not nearly equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percent` is not in `[0, 100]`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn at_most_percent(values: &'a L, percent: f64) -> Outliers<'a, L> {
+        assert!(
+            (0.0..=100.0).contains(&percent),
+            "percent must be in [0, 100], got {}",
+            percent
+        );
+        Outliers {
+            values,
+            mode: Mode::Percent(percent),
+        }
+    }
+
+    /// Passes if the element at the `percentile`-th percentile of the absolute error is nearly equal.
+    ///
+    /// The percentile uses the nearest-rank method, so `100.0` requires every element to be nearly equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is not in `[0, 100]`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn percentile(values: &'a L, percentile: f64) -> Outliers<'a, L> {
+        assert!(
+            (0.0..=100.0).contains(&percentile),
+            "percentile must be in [0, 100], got {}",
+            percentile
+        );
+        Outliers {
+            values,
+            mode: Mode::Percentile(percentile),
+        }
+    }
+
+    /// Returns the indices of the elements that are not nearly equal, or `None` if the lengths differ.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn outliers<R, B>(&self, other: &R, eps: &B) -> Option<Vec<usize>>
+    where
+        R: Sequence + ?Sized,
+        L::Item: NearlyEq<R::Item, B>,
+    {
+        if self.values.len() != other.len() {
+            return None;
+        }
+        Some(
+            self.values
+                .elements()
+                .zip(other.elements())
+                .enumerate()
+                .filter(|&(_, (x, y))| x.ne(y, eps))
+                .map(|(i, _)| i)
+                .collect(),
+        )
+    }
+
+    fn percentile_index<R, B>(&self, other: &R, eps: &B, percentile: f64) -> Option<(usize, bool)>
+    where
+        R: Sequence + ?Sized,
+        L::Item: NearlyEq<R::Item, B>,
+    {
+        let mut errors: Vec<(f64, usize, bool)> = self
+            .values
+            .elements()
+            .zip(other.elements())
+            .enumerate()
+            .map(|(i, (x, y))| {
+                let passed = x.eq(y, eps);
+                let error = match x.deviation(y) {
+                    Some(deviation) => deviation.abs,
+                    None if passed => 0.0,
+                    None => f64::INFINITY,
+                };
+                (error, i, passed)
+            })
+            .collect();
+        if errors.is_empty() {
+            return None;
+        }
+        errors.sort_by(|a, b| a.0.total_cmp(&b.0).then((!a.2).cmp(&!b.2)));
+        let rank = (percentile / 100.0 * errors.len() as f64).ceil() as usize;
+        let (_, i, passed) = errors[rank.max(1).min(errors.len()) - 1];
+        Some((i, passed))
+    }
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for Outliers<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L, R> NearlyEq<R, B> for Outliers<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence + ?Sized,
+    L::Item: NearlyEq<R::Item, B>,
+{
+    fn eq(&self, other: &R, eps: &B) -> bool {
+        let outliers = match self.outliers(other, eps) {
+            Some(outliers) => outliers,
+            None => return false,
+        };
+        match self.mode {
+            Mode::Count(count) => outliers.len() <= count,
+            Mode::Percent(percent) => {
+                outliers.len() as f64 * 100.0 <= percent * self.values.len() as f64
+            }
+            Mode::Percentile(percentile) => match self.percentile_index(other, eps, percentile) {
+                Some((_, passed)) => passed,
+                None => true,
+            },
+        }
+    }
+
+    fn stats(&self, other: &R, eps: &B) -> Option<ComparisonStats> {
+        if self.values.len() != other.len() {
+            None
+        } else {
+            Some(ComparisonStats::from_pairs(
+                self.values.elements().zip(other.elements()),
+                eps,
+            ))
+        }
+    }
+
+    fn explain(&self, other: &R, eps: &B) -> Option<String> {
+        let outliers = self.outliers(other, eps)?;
+        let mut message = format!("{} outlier(s) at index {:?}", outliers.len(), outliers);
+        match self.mode {
+            Mode::Count(count) => message.push_str(&format!(", at most {} allowed", count)),
+            Mode::Percent(percent) => message.push_str(&format!(", at most {}% allowed", percent)),
+            Mode::Percentile(percentile) => {
+                if let Some((i, _)) = self.percentile_index(other, eps, percentile) {
+                    message.push_str(&format!(", {}th percentile at index {}", percentile, i));
+                }
+            }
+        }
+        if let Some(stats) = self.stats(other, eps) {
+            message.push_str(&format!("\n{}", stats));
+        }
+        Some(message)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L> DefaultTolerance<B> for Outliers<'a, L>
+where
+    L: Sequence + ?Sized,
+    L::Item: DefaultTolerance<B>,
+{
+    fn default_tolerance() -> B {
+        L::Item::default_tolerance()
+    }
+}
@@ -5,15 +5,18 @@
 
 use num_rational::Ratio;
 use num_integer::Integer;
-use num_traits::identities::Zero;
-use NearlyEq;
+use num_traits::identities::{One, Zero};
+use {DefaultDiff, DefaultTolerance, NearlyEq, NearlyOrd, NearlyZero, RequiredTolerance, Tolerance};
 
-#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.2.1"))]
-impl<A: Integer + Clone> NearlyEq<Ratio<A>, Ratio<A>> for Ratio<A> {
-    fn eps() -> Ratio<A> {
+#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.3.0"))]
+impl<A: Integer + Clone> DefaultTolerance for Ratio<A> {
+    fn default_tolerance() -> Ratio<A> {
         Ratio::zero()
     }
+}
 
+#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.2.1"))]
+impl<A: Integer + Clone> NearlyEq<Ratio<A>, Ratio<A>> for Ratio<A> {
     fn eq(&self, other: &Ratio<A>, eps: &Ratio<A>) -> bool {
         let diff = if *self > *other {
             self.clone() - other.clone()
@@ -28,3 +31,39 @@ impl<A: Integer + Clone> NearlyEq<Ratio<A>, Ratio<A>> for Ratio<A> {
         }
     }
 }
+
+#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.3.0"))]
+impl<A: Integer + Clone> RequiredTolerance<Ratio<A>, Ratio<A>> for Ratio<A> {
+    fn required_tolerance(&self, other: &Ratio<A>) -> Tolerance<Ratio<A>> {
+        if *self == *other {
+            Tolerance::Any
+        } else if *self > *other {
+            Tolerance::Above(self.clone() - other.clone())
+        } else {
+            Tolerance::Above(other.clone() - self.clone())
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.3.0"))]
+impl<A: Integer + Clone> NearlyOrd<Ratio<A>, Ratio<A>> for Ratio<A> {}
+
+#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.3.0"))]
+impl<A: Integer + Clone> NearlyZero<Ratio<A>> for Ratio<A> {
+    fn is_nearly_zero(&self, eps: &Ratio<A>) -> bool {
+        NearlyEq::eq(self, &Ratio::zero(), eps)
+    }
+
+    fn is_nearly_integer(&self, eps: &Ratio<A>) -> bool {
+        NearlyEq::eq(self, &self.round(), eps)
+    }
+
+    fn is_nearly_one(&self, eps: &Ratio<A>) -> bool {
+        NearlyEq::eq(self, &Ratio::one(), eps)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "rational", since = "0.3.0"))]
+impl<A: Integer + Clone> DefaultDiff for Ratio<A> {
+    type Diff = Ratio<A>;
+}
@@ -0,0 +1,535 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::cmp::Ordering;
+use std::env;
+use std::fmt::{Debug, Write as FmtWrite};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, RwLock};
+
+use {calibrate, near_miss};
+use {Deviation, NearlyEq, NearlyOrd, NearlyZero, Target};
+
+/// Environment variable naming the file `JsonLinesReporter::from_env` appends to.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub const JSON_REPORT_ENV: &str = "NEARLY_EQ_JSON_REPORT";
+
+/// Environment variable naming the file `JUnitReporter::from_env` writes to.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub const JUNIT_REPORT_ENV: &str = "NEARLY_EQ_JUNIT_REPORT";
+
+/// Environment variable that makes the reporters created from the environment record passed comparisons too.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub const REPORT_PASSED_ENV: &str = "NEARLY_EQ_REPORT_PASSED";
+
+/// A single nearly(approximately) equality comparison made by an assertion.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Comparison {
+    /// Whether the assertion held.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub passed: bool,
+    /// The asserted relation, such as `(left == right)` or `value is nearly zero`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub assertion: &'static str,
+    /// The left value, formatted with `Debug`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub left: String,
+    /// The right value, formatted with `Debug`, or what the left value was tested against.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub right: String,
+    /// The diff value, formatted with `Debug`, or `default` if the assertion used the default diff value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub eps: String,
+    /// The difference as a ratio of the diff value, see `NearlyEq::margin`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub margin: Option<f64>,
+    /// The location of the assertion.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub location: &'static Location<'static>,
+}
+
+/// Receives the comparisons made by the assertion macros.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait Reporter: Send + Sync {
+    /// This method is called for every failed comparison, and for passed ones if `report_passed` returns true.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn report(&self, comparison: &Comparison);
+
+    /// This method tests whether passed comparisons should be reported too.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn report_passed(&self) -> bool {
+        false
+    }
+}
+
+static REPORTER: RwLock<Option<Box<dyn Reporter>>> = RwLock::new(None);
+static FROM_ENV: Once = Once::new();
+
+/// Sets the global reporter, returning the previous one.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn set_reporter(reporter: Box<dyn Reporter>) -> Option<Box<dyn Reporter>> {
+    FROM_ENV.call_once(|| {});
+    REPORTER
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(reporter)
+}
+
+/// Removes the global reporter, returning it.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn take_reporter() -> Option<Box<dyn Reporter>> {
+    FROM_ENV.call_once(|| {});
+    REPORTER.write().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+/// Creates the reporters configured by `NEARLY_EQ_JSON_REPORT` and `NEARLY_EQ_JUNIT_REPORT`.
+///
+/// Returns `None` if neither variable is set.
+/// This is done automatically by the first assertion if no reporter has been set.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn reporter_from_env() -> Option<Box<dyn Reporter>> {
+    let mut reporters: Vec<Box<dyn Reporter>> = Vec::new();
+    if let Some(reporter) = JsonLinesReporter::from_env() {
+        reporters.push(Box::new(reporter));
+    }
+    if let Some(reporter) = JUnitReporter::from_env() {
+        reporters.push(Box::new(reporter));
+    }
+    match reporters.len() {
+        0 => None,
+        1 => reporters.pop(),
+        _ => Some(Box::new(Reporters(reporters))),
+    }
+}
+
+struct Reporters(Vec<Box<dyn Reporter>>);
+
+impl Reporter for Reporters {
+    fn report(&self, comparison: &Comparison) {
+        for reporter in &self.0 {
+            if comparison.passed && !reporter.report_passed() {
+                continue;
+            }
+            reporter.report(comparison);
+        }
+    }
+
+    fn report_passed(&self) -> bool {
+        self.0.iter().any(|r| r.report_passed())
+    }
+}
+
+fn report_passed_from_env() -> bool {
+    env::var(REPORT_PASSED_ENV)
+        .map(|v| !v.is_empty() && v != "0")
+        .unwrap_or(false)
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check<A, B, C>(a: &C, b: &A, eps: &B) -> bool
+where
+    A: Debug + ?Sized,
+    B: Debug,
+    C: NearlyEq<A, B> + Debug + ?Sized,
+{
+    let passed = a.eq(b, eps);
+    observe_pair(a, b, eps, passed, true, "(left == right)", || format!("{:?}", eps))
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_default<A, B, C>(a: &C, b: &A, eps: &B) -> bool
+where
+    A: Debug + ?Sized,
+    C: NearlyEq<A, B> + Debug + ?Sized,
+{
+    let passed = a.eq(b, eps);
+    observe_pair(a, b, eps, passed, true, "(left == right)", || "default".to_string())
+}
+
+// `order` is `Ordering::Less` for `left <= right` and `Ordering::Greater` for `left >= right`.
+#[doc(hidden)]
+#[track_caller]
+pub fn check_order<A, B, C>(a: &C, b: &A, eps: &B, order: Ordering) -> bool
+where
+    A: Debug + ?Sized,
+    B: Debug,
+    C: NearlyOrd<A, B> + Debug + ?Sized,
+{
+    let (passed, inverted) = ordered(a, b, eps, order);
+    observe_pair(a, b, eps, passed, inverted, order_assertion(order), || format!("{:?}", eps))
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_order_default<A, B, C>(a: &C, b: &A, eps: &B, order: Ordering) -> bool
+where
+    A: Debug + ?Sized,
+    C: NearlyOrd<A, B> + Debug + ?Sized,
+{
+    let (passed, inverted) = ordered(a, b, eps, order);
+    observe_pair(a, b, eps, passed, inverted, order_assertion(order), || "default".to_string())
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_target<B, C>(a: &C, eps: &B, target: Target) -> bool
+where
+    B: Debug,
+    C: NearlyZero<B> + Debug + ?Sized,
+{
+    observe_target(a, eps, target, || format!("{:?}", eps))
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_target_default<B, C>(a: &C, eps: &B, target: Target) -> bool
+where
+    C: NearlyZero<B> + Debug + ?Sized,
+{
+    observe_target(a, eps, target, || "default".to_string())
+}
+
+// Returns whether the order nearly holds, and whether the values are not strictly in that order.
+fn ordered<A, B, C>(a: &C, b: &A, eps: &B, order: Ordering) -> (bool, bool)
+where
+    A: ?Sized,
+    C: NearlyOrd<A, B> + ?Sized,
+{
+    let passed = if order == Ordering::Less {
+        a.nearly_le(b, eps)
+    } else {
+        a.nearly_ge(b, eps)
+    };
+    (passed, a.partial_cmp(b) != Some(order))
+}
+
+fn order_assertion(order: Ordering) -> &'static str {
+    if order == Ordering::Less {
+        "(left <= right)"
+    } else {
+        "(left >= right)"
+    }
+}
+
+// The difference only counts if `inverted`, otherwise the values are strictly in the asserted order.
+#[track_caller]
+fn observe_pair<A, B, C, F>(
+    a: &C,
+    b: &A,
+    eps: &B,
+    passed: bool,
+    inverted: bool,
+    assertion: &'static str,
+    eps_text: F,
+) -> bool
+where
+    A: Debug + ?Sized,
+    C: NearlyEq<A, B> + Debug + ?Sized,
+    F: FnOnce() -> String,
+{
+    observe(
+        passed,
+        assertion,
+        Location::caller(),
+        || if inverted { a.margin(b, eps) } else { Some(0.0) },
+        || if inverted { a.deviation(b) } else { None },
+        || (format!("{:?}", a), format!("{:?}", b), eps_text()),
+    )
+}
+
+#[track_caller]
+fn observe_target<B, C, F>(a: &C, eps: &B, target: Target, eps_text: F) -> bool
+where
+    C: NearlyZero<B> + Debug + ?Sized,
+    F: FnOnce() -> String,
+{
+    let (passed, assertion, right) = match target {
+        Target::Zero => (a.is_nearly_zero(eps), "value is nearly zero", "zero"),
+        Target::Integer => (a.is_nearly_integer(eps), "value is nearly an integer", "the nearest integer"),
+        Target::One => (a.is_nearly_one(eps), "value is nearly one", "one"),
+    };
+    observe(
+        passed,
+        assertion,
+        Location::caller(),
+        || a.margin_from(target, eps),
+        || a.deviation_from(target),
+        || (format!("{:?}", a), right.to_string(), eps_text()),
+    )
+}
+
+// Passes an assertion to the calibration, near-miss and reporter hooks.
+// `margin` and `deviation` describe the difference the assertion forgave, and
+// `values` returns the left value, the right value and the diff value as text.
+pub(crate) fn observe<M, D, V>(
+    passed: bool,
+    assertion: &'static str,
+    location: &'static Location<'static>,
+    margin: M,
+    deviation: D,
+    values: V,
+) -> bool
+where
+    M: Fn() -> Option<f64>,
+    D: FnOnce() -> Option<Deviation>,
+    V: FnOnce() -> (String, String, String),
+{
+    FROM_ENV.call_once(|| {
+        if let Some(reporter) = reporter_from_env() {
+            *REPORTER.write().unwrap_or_else(|e| e.into_inner()) = Some(reporter);
+        }
+    });
+    near_miss::init_from_env();
+    calibrate::init_from_env();
+    if calibrate::is_enabled() {
+        calibrate::observe(deviation(), location);
+    }
+    let near_miss_margin = if passed && near_miss::is_enabled() {
+        let margin = margin();
+        near_miss::observe(margin, location);
+        Some(margin)
+    } else {
+        None
+    };
+    let reporter = REPORTER.read().unwrap_or_else(|e| e.into_inner());
+    if let Some(ref reporter) = *reporter {
+        if !passed || reporter.report_passed() {
+            let (left, right, eps) = values();
+            reporter.report(&Comparison {
+                passed,
+                assertion,
+                left,
+                right,
+                eps,
+                margin: near_miss_margin.unwrap_or_else(margin),
+                location,
+            });
+        }
+    }
+    passed
+}
+
+#[doc(hidden)]
+pub fn details<A, B, C>(a: &C, b: &A, eps: &B) -> String
+where
+    A: ?Sized,
+    C: NearlyEq<A, B> + ?Sized,
+{
+    match a.explain(b, eps) {
+        Some(explanation) => format!("\n{}", explanation),
+        None => String::new(),
+    }
+}
+
+/// A reporter appending one JSON object per comparison to a file.
+#[derive(Debug)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct JsonLinesReporter {
+    path: PathBuf,
+    passed: bool,
+    lock: Mutex<()>,
+}
+
+impl JsonLinesReporter {
+    /// Creates a reporter appending to the given file.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new<P: AsRef<Path>>(path: P) -> JsonLinesReporter {
+        JsonLinesReporter {
+            path: path.as_ref().to_path_buf(),
+            passed: false,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Creates a reporter appending to the file named by `NEARLY_EQ_JSON_REPORT`.
+    ///
+    /// Passed comparisons are recorded if `NEARLY_EQ_REPORT_PASSED` is set.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn from_env() -> Option<JsonLinesReporter> {
+        env::var_os(JSON_REPORT_ENV)
+            .map(|path| JsonLinesReporter::new(path).with_passed(report_passed_from_env()))
+    }
+
+    /// Sets whether passed comparisons are recorded.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn with_passed(mut self, passed: bool) -> JsonLinesReporter {
+        self.passed = passed;
+        self
+    }
+
+    fn write(&self, comparison: &Comparison) -> io::Result<()> {
+        let mut line = String::new();
+        let _ = write!(
+            line,
+            "{{\"passed\":{},\"assertion\":{},\"left\":{},\"right\":{},\"eps\":{},\"margin\":{},\"file\":{},\"line\":{},\"column\":{}}}",
+            comparison.passed,
+            json_string(comparison.assertion),
+            json_string(&comparison.left),
+            json_string(&comparison.right),
+            json_string(&comparison.eps),
+            match comparison.margin {
+                Some(m) if m.is_finite() => m.to_string(),
+                _ => "null".to_string(),
+            },
+            json_string(comparison.location.file()),
+            comparison.location.line(),
+            comparison.location.column()
+        );
+        line.push('\n');
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+impl Reporter for JsonLinesReporter {
+    fn report(&self, comparison: &Comparison) {
+        if let Err(e) = self.write(comparison) {
+            eprintln!("nearly_eq: cannot write to {}: {}", self.path.display(), e);
+        }
+    }
+
+    fn report_passed(&self) -> bool {
+        self.passed
+    }
+}
+
+/// A reporter writing the comparisons as a JUnit XML test suite.
+///
+/// Each comparison becomes a test case named after its location.
+/// The file is rewritten after every reported comparison, so it is complete whenever the process exits.
+#[derive(Debug)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct JUnitReporter {
+    path: PathBuf,
+    passed: bool,
+    cases: Mutex<Vec<Comparison>>,
+}
+
+impl JUnitReporter {
+    /// Creates a reporter writing to the given file.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new<P: AsRef<Path>>(path: P) -> JUnitReporter {
+        JUnitReporter {
+            path: path.as_ref().to_path_buf(),
+            passed: false,
+            cases: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates a reporter writing to the file named by `NEARLY_EQ_JUNIT_REPORT`.
+    ///
+    /// Passed comparisons are recorded if `NEARLY_EQ_REPORT_PASSED` is set.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn from_env() -> Option<JUnitReporter> {
+        env::var_os(JUNIT_REPORT_ENV)
+            .map(|path| JUnitReporter::new(path).with_passed(report_passed_from_env()))
+    }
+
+    /// Sets whether passed comparisons are recorded.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn with_passed(mut self, passed: bool) -> JUnitReporter {
+        self.passed = passed;
+        self
+    }
+
+    fn write(&self, cases: &[Comparison]) -> io::Result<()> {
+        let failures = cases.iter().filter(|c| !c.passed).count();
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuite name=\"nearly_eq\" tests=\"{}\" failures=\"{}\">",
+            cases.len(),
+            failures
+        );
+        for case in cases {
+            let location = case.location;
+            let _ = write!(
+                xml,
+                "  <testcase classname=\"{}\" name=\"{}\"",
+                xml_escape(location.file()),
+                xml_escape(&location.to_string())
+            );
+            if case.passed {
+                xml.push_str("/>\n");
+            } else {
+                let _ = write!(
+                    xml,
+                    ">\n    <failure message=\"assertion failed: `{}`\">left: {}\nright: {}\neps: {}",
+                    xml_escape(case.assertion),
+                    xml_escape(&case.left),
+                    xml_escape(&case.right),
+                    xml_escape(&case.eps)
+                );
+                if let Some(margin) = case.margin {
+                    let _ = write!(xml, "\nmargin: {}", margin);
+                }
+                xml.push_str("</failure>\n  </testcase>\n");
+            }
+        }
+        xml.push_str("</testsuite>\n");
+        File::create(&self.path)?.write_all(xml.as_bytes())
+    }
+}
+
+impl Reporter for JUnitReporter {
+    fn report(&self, comparison: &Comparison) {
+        let mut cases = self.cases.lock().unwrap_or_else(|e| e.into_inner());
+        cases.push(comparison.clone());
+        if let Err(e) = self.write(&cases) {
+            eprintln!("nearly_eq: cannot write to {}: {}", self.path.display(), e);
+        }
+    }
+
+    fn report_passed(&self) -> bool {
+        self.passed
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,79 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+/// Trait for collections whose elements can be visited in order by the comparison adapters.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait Sequence {
+    /// The element type.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    type Item;
+
+    /// This method returns the number of elements.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn len(&self) -> usize;
+
+    /// This method returns an iterator over the elements.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn elements<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Self::Item> + 'a>;
+
+    /// This method tests for no elements.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T> Sequence for [T] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn elements<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T> Sequence for Vec<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn elements<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, const N: usize> Sequence for [T; N] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn elements<'a>(&'a self) -> Box<dyn Iterator<Item = &'a T> + 'a> {
+        Box::new(self.iter())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<S: Sequence + ?Sized> Sequence for &S {
+    type Item = S::Item;
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn elements<'a>(&'a self) -> Box<dyn Iterator<Item = &'a S::Item> + 'a> {
+        (**self).elements()
+    }
+}
@@ -0,0 +1,168 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::cmp::Ordering;
+
+use {NearlyEq, NearlyOrd};
+
+/// Extension trait for searching slices for nearly(approximately) equal values.
+///
+/// # Examples
+///
+/// ```rust
+/// # use nearly_eq::NearlySliceExt;
+/// let grid: Vec<f64> = (0..10).map(|i| i as f64 * 0.1).collect();
+/// assert!(grid.nearly_contains(&0.3, &1e-12));
+/// assert_eq!(grid.nearly_position(&0.3, &1e-12), Some(3));
+/// assert_eq!(grid.nearly_binary_search(&0.3, &1e-12), Ok(3));
+/// assert_eq!(grid.nearly_binary_search(&0.35, &1e-12), Err(4));
+///
+/// let points = [[0f64, 0.0], [1.0, 1.0], [1e-13, 0.0], [1.0, 1.0 + 1e-13]];
+/// assert_eq!(points.nearly_cluster(&1e-12), vec![vec![0, 2], vec![1, 3]]);
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait NearlySliceExt<T> {
+    /// This method tests for an element nearly(approximately) equal to the given value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_contains<A: ?Sized, B>(&self, x: &A, eps: &B) -> bool
+    where
+        T: NearlyEq<A, B>;
+
+    /// This method returns the index of the first element nearly(approximately) equal to the given value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_position<A: ?Sized, B>(&self, x: &A, eps: &B) -> Option<usize>
+    where
+        T: NearlyEq<A, B>;
+
+    /// This method returns the index of the last element nearly(approximately) equal to the given value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_rposition<A: ?Sized, B>(&self, x: &A, eps: &B) -> Option<usize>
+    where
+        T: NearlyEq<A, B>;
+
+    /// This method binary searches a sorted slice for an element nearly(approximately) equal to the given value.
+    ///
+    /// Like `slice::binary_search`, returns `Ok` with the index of a matching element, or
+    /// `Err` with the index where the value could be inserted to keep the slice sorted.
+    /// Elements that cannot be compared with the value, such as `NaN`, are treated as greater.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_binary_search<A: ?Sized, B>(&self, x: &A, eps: &B) -> Result<usize, usize>
+    where
+        T: NearlyOrd<A, B>;
+
+    /// This method groups the elements into clusters of nearly(approximately) equal values by single linkage.
+    ///
+    /// Two elements are in the same cluster if they are connected by a chain of nearly equal elements,
+    /// so the result does not depend on the order of the elements. Each cluster lists the indices
+    /// of its elements in ascending order, and the clusters are ordered by their first index.
+    /// The first element of a cluster is its representative.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_cluster<B>(&self, eps: &B) -> Vec<Vec<usize>>
+    where
+        T: NearlyEq<T, B>;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T> NearlySliceExt<T> for [T] {
+    fn nearly_contains<A: ?Sized, B>(&self, x: &A, eps: &B) -> bool
+    where
+        T: NearlyEq<A, B>,
+    {
+        self.iter().any(|y| NearlyEq::eq(y, x, eps))
+    }
+
+    fn nearly_position<A: ?Sized, B>(&self, x: &A, eps: &B) -> Option<usize>
+    where
+        T: NearlyEq<A, B>,
+    {
+        self.iter().position(|y| NearlyEq::eq(y, x, eps))
+    }
+
+    fn nearly_rposition<A: ?Sized, B>(&self, x: &A, eps: &B) -> Option<usize>
+    where
+        T: NearlyEq<A, B>,
+    {
+        self.iter().rposition(|y| NearlyEq::eq(y, x, eps))
+    }
+
+    fn nearly_binary_search<A: ?Sized, B>(&self, x: &A, eps: &B) -> Result<usize, usize>
+    where
+        T: NearlyOrd<A, B>,
+    {
+        self.binary_search_by(|y| y.nearly_cmp(x, eps).unwrap_or(Ordering::Greater))
+    }
+
+    fn nearly_cluster<B>(&self, eps: &B) -> Vec<Vec<usize>>
+    where
+        T: NearlyEq<T, B>,
+    {
+        let mut roots: Vec<usize> = (0..self.len()).collect();
+        for i in 0..self.len() {
+            for j in i + 1..self.len() {
+                if NearlyEq::eq(&self[i], &self[j], eps) {
+                    let (a, b) = (root(&mut roots, i), root(&mut roots, j));
+                    roots[a.max(b)] = a.min(b);
+                }
+            }
+        }
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        let mut cluster_of = vec![0; self.len()];
+        for i in 0..self.len() {
+            let r = root(&mut roots, i);
+            if r == i {
+                cluster_of[i] = clusters.len();
+                clusters.push(vec![i]);
+            } else {
+                clusters[cluster_of[r]].push(i);
+            }
+        }
+        clusters
+    }
+}
+
+// Finds the smallest index in the cluster of `i`, compressing the path on the way.
+fn root(roots: &mut [usize], mut i: usize) -> usize {
+    while roots[i] != i {
+        roots[i] = roots[roots[i]];
+        i = roots[i];
+    }
+    i
+}
+
+/// Extension trait for removing nearly(approximately) equal values from vectors.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait NearlyVecExt<T> {
+    /// This method removes consecutive nearly(approximately) equal elements, keeping the first of each run.
+    ///
+    /// Each element is compared with its original predecessor, so on sorted input a run is a
+    /// single-linkage cluster of `nearly_cluster` and the kept element is its representative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use nearly_eq::NearlyVecExt;
+    /// let mut roots = vec![1f64, 1.0 + 1e-13, 2.0, 2.0 + 1e-13, 2.0 + 2e-13, 3.0];
+    /// roots.nearly_dedup(&1.5e-13);
+    /// assert_eq!(roots, vec![1f64, 2.0, 3.0]);
+    /// ```
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn nearly_dedup<B>(&mut self, eps: &B)
+    where
+        T: NearlyEq<T, B>;
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T> NearlyVecExt<T> for Vec<T> {
+    fn nearly_dedup<B>(&mut self, eps: &B)
+    where
+        T: NearlyEq<T, B>,
+    {
+        let keep: Vec<bool> = (0..self.len())
+            .map(|i| i == 0 || !NearlyEq::eq(&self[i - 1], &self[i], eps))
+            .collect();
+        let mut keep = keep.into_iter();
+        self.retain(|_| keep.next().unwrap_or(true));
+    }
+}
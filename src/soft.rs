@@ -0,0 +1,134 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt::Debug;
+use std::mem;
+use std::panic::Location;
+use std::thread;
+
+use report::{check, details};
+use {DefaultTolerance, NearlyEq};
+
+/// Collects nearly(approximately) equality failures and panics once with a combined report.
+///
+/// Failures are reported when `finish` is called or when the guard is dropped.
+///
+/// # Examples
+///
+/// ```should_panic
+/// let mut s = nearly_eq::soft();
+/// s.check(&1f64, &1.5f64); // recorded, does not panic
+/// s.check(&2f64, &2f64);
+/// s.check(&3f64, &4f64); // recorded, does not panic
+/// s.finish(); // panics, listing both failures
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct SoftAsserts {
+    failures: Vec<String>,
+}
+
+/// Creates a new `SoftAsserts` guard.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub fn soft() -> SoftAsserts {
+    SoftAsserts::new()
+}
+
+impl SoftAsserts {
+    /// Creates a new guard with no recorded failures.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new() -> SoftAsserts {
+        SoftAsserts {
+            failures: Vec::new(),
+        }
+    }
+
+    /// Checks that two values are nearly equal, using `DefaultTolerance::default_tolerance()` as the diff value.
+    ///
+    /// Returns the result of the comparison. A failure is recorded with the caller's location.
+    #[track_caller]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn check<A, B, C>(&mut self, a: &C, b: &A) -> bool
+    where
+        A: Debug + ?Sized,
+        B: Debug,
+        C: NearlyEq<A, B> + DefaultTolerance<B> + Debug + ?Sized,
+    {
+        let eps = C::default_tolerance();
+        if check(a, b, &eps) {
+            true
+        } else {
+            let location = Location::caller();
+            self.failures.push(format!(
+                "{}: `(left == right)` (left: `{:?}` , right: `{:?}`){}",
+                location,
+                a,
+                b,
+                details(a, b, &eps)
+            ));
+            false
+        }
+    }
+
+    /// Checks that two values are nearly equal with an explicit diff value.
+    ///
+    /// Returns the result of the comparison. A failure is recorded with the caller's location.
+    #[track_caller]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn check_eps<A, B, C>(&mut self, a: &C, b: &A, eps: &B) -> bool
+    where
+        A: Debug + ?Sized,
+        B: Debug,
+        C: NearlyEq<A, B> + Debug + ?Sized,
+    {
+        if check(a, b, eps) {
+            true
+        } else {
+            let location = Location::caller();
+            self.failures.push(format!(
+                "{}: `(left == right)` (left: `{:?}` , right: `{:?}`, eps: `{:?}`){}",
+                location,
+                a,
+                b,
+                eps,
+                details(a, b, eps)
+            ));
+            false
+        }
+    }
+
+    /// Returns the failures recorded so far.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+
+    /// Panics with a combined report if any check has failed.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn finish(mut self) {
+        let failures = mem::take(&mut self.failures);
+        if !failures.is_empty() {
+            panic!("{}", report(&failures));
+        }
+    }
+}
+
+impl Drop for SoftAsserts {
+    fn drop(&mut self) {
+        if !self.failures.is_empty() && !thread::panicking() {
+            let failures = mem::take(&mut self.failures);
+            panic!("{}", report(&failures));
+        }
+    }
+}
+
+fn report(failures: &[String]) -> String {
+    let mut message = format!("{} nearly equality assertion(s) failed:", failures.len());
+    for failure in failures {
+        message.push_str("\n    ");
+        message.push_str(failure);
+    }
+    message
+}
@@ -0,0 +1,93 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use NearlyEq;
+
+/// Summary of an elementwise nearly(approximately) equality comparison of two collections.
+///
+/// The errors are taken from `NearlyEq::deviation` of each element pair;
+/// pairs whose difference cannot be measured only count towards `count` and `mismatches`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct ComparisonStats {
+    /// The number of compared elements.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub count: usize,
+    /// The number of elements that are not nearly equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub mismatches: usize,
+    /// The largest absolute error.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub max_abs_error: f64,
+    /// The index of the element with the largest absolute error.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub max_abs_index: Option<usize>,
+    /// The largest relative error.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub max_rel_error: f64,
+    /// The mean of the absolute errors.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub mean_abs_error: f64,
+    /// The root mean square of the absolute errors.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub rms_error: f64,
+}
+
+impl ComparisonStats {
+    /// Compares the element pairs in a single pass.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn from_pairs<'a, A, B, C, I>(pairs: I, eps: &B) -> ComparisonStats
+    where
+        A: 'a,
+        C: NearlyEq<A, B> + 'a,
+        I: IntoIterator<Item = (&'a C, &'a A)>,
+    {
+        let mut stats = ComparisonStats::default();
+        let mut measured = 0usize;
+        let mut sum = 0f64;
+        let mut sum_sq = 0f64;
+        for (i, (x, y)) in pairs.into_iter().enumerate() {
+            stats.count += 1;
+            if x.ne(y, eps) {
+                stats.mismatches += 1;
+            }
+            if let Some(deviation) = x.deviation(y) {
+                measured += 1;
+                sum += deviation.abs;
+                sum_sq += deviation.abs * deviation.abs;
+                if stats.max_abs_index.is_none() || deviation.abs > stats.max_abs_error {
+                    stats.max_abs_error = deviation.abs;
+                    stats.max_abs_index = Some(i);
+                }
+                stats.max_rel_error = stats.max_rel_error.max(deviation.rel);
+            }
+        }
+        if measured > 0 {
+            stats.mean_abs_error = sum / measured as f64;
+            stats.rms_error = (sum_sq / measured as f64).sqrt();
+        }
+        stats
+    }
+}
+
+impl fmt::Display for ComparisonStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} element(s), {} mismatch(es), max abs error {:e}",
+            self.count, self.mismatches, self.max_abs_error
+        )?;
+        if let Some(i) = self.max_abs_index {
+            write!(f, " at index {}", i)?;
+        }
+        write!(
+            f,
+            ", max rel error {:e}, mean abs error {:e}, rms error {:e}",
+            self.max_rel_error, self.mean_abs_error, self.rms_error
+        )
+    }
+}
@@ -0,0 +1,260 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::cell::{Cell, RefCell};
+use std::convert::TryFrom;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+use NearlyEq;
+
+/// The smallest diff value under which two values compare nearly(approximately) equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub enum Tolerance<D> {
+    /// The values compare nearly equal under any diff value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Any,
+    /// The values compare nearly equal under any diff value greater than this one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Above(D),
+    /// The values compare nearly equal under any diff value greater than or equal to this one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    AtLeast(D),
+    /// The values never compare nearly equal.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Never,
+}
+
+impl<D: PartialOrd> Tolerance<D> {
+    /// Returns the stricter of the two tolerances, the one both values need.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn max(self, other: Tolerance<D>) -> Tolerance<D> {
+        match (self, other) {
+            (Tolerance::Never, _) | (_, Tolerance::Never) => Tolerance::Never,
+            (Tolerance::Any, x) | (x, Tolerance::Any) => x,
+            (x, y) => {
+                if x.value() > y.value() {
+                    x
+                } else if y.value() > x.value() {
+                    y
+                } else {
+                    match (x, y) {
+                        (Tolerance::Above(d), _) | (_, Tolerance::Above(d)) => Tolerance::Above(d),
+                        (x, _) => x,
+                    }
+                }
+            }
+        }
+    }
+
+    /// This method tests whether the values compare nearly equal under the given diff value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn is_satisfied_by(&self, eps: &D) -> bool {
+        match *self {
+            Tolerance::Any => true,
+            Tolerance::Above(ref d) => *eps > *d,
+            Tolerance::AtLeast(ref d) => *eps >= *d,
+            Tolerance::Never => false,
+        }
+    }
+
+    fn value(&self) -> Option<&D> {
+        match *self {
+            Tolerance::Above(ref d) | Tolerance::AtLeast(ref d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+/// Trait for querying the smallest diff value under which two values compare nearly(approximately) equal.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait RequiredTolerance<Rhs: ?Sized = Self, Diff = Self>: NearlyEq<Rhs, Diff> {
+    /// This method returns the smallest diff value under which `self.eq(other, eps)` holds.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn required_tolerance(&self, other: &Rhs) -> Tolerance<Diff>;
+
+    /// This method returns how many representable values apart self and other values are,
+    /// for floating and fixed point types.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn required_ulps(&self, other: &Rhs) -> Option<u64> {
+        self.deviation(other).and_then(|d| d.ulps)
+    }
+}
+
+pub(crate) fn max_tolerance<D, I>(tolerances: I) -> Tolerance<D>
+where
+    D: PartialOrd,
+    I: IntoIterator<Item = Tolerance<D>>,
+{
+    let mut max = Tolerance::Any;
+    for tolerance in tolerances {
+        max = max.max(tolerance);
+        if max == Tolerance::Never {
+            break;
+        }
+    }
+    max
+}
+
+macro_rules! float_impls {
+    ($($T:ty)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl RequiredTolerance for $T {
+                fn required_tolerance(&self, other: &$T) -> Tolerance<$T> {
+                    if *self == *other {
+                        Tolerance::Any
+                    } else {
+                        let diff = (*self - *other).abs();
+                        if diff.is_finite() {
+                            Tolerance::Above(diff)
+                        } else {
+                            Tolerance::Never
+                        }
+                    }
+                }
+            }
+        )+
+    }
+}
+
+float_impls! { f32 f64 }
+
+macro_rules! itype_impls {
+    ($($T:ty)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl RequiredTolerance for $T {
+                fn required_tolerance(&self, other: &$T) -> Tolerance<$T> {
+                    if *self == *other {
+                        Tolerance::Any
+                    } else {
+                        match <$T>::try_from(self.abs_diff(*other)) {
+                            Ok(diff) => Tolerance::Above(diff),
+                            Err(_) => Tolerance::Never,
+                        }
+                    }
+                }
+            }
+        )+
+    }
+}
+
+itype_impls! { i8 i16 i32 i64 }
+
+#[cfg(feature = "i128")]
+itype_impls! { i128 }
+
+macro_rules! utype_impls {
+    ($($T:ty)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl RequiredTolerance for $T {
+                fn required_tolerance(&self, other: &$T) -> Tolerance<$T> {
+                    if *self == *other {
+                        Tolerance::Any
+                    } else {
+                        Tolerance::Above(if *self > *other { *self - *other } else { *other - *self })
+                    }
+                }
+            }
+        )+
+    }
+}
+
+utype_impls! { u8 u16 u32 u64 }
+
+#[cfg(feature = "i128")]
+utype_impls! { u128 }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B: PartialOrd, C: RequiredTolerance<A, B>> RequiredTolerance<[A], B> for [C] {
+    fn required_tolerance(&self, other: &[A]) -> Tolerance<B> {
+        if self.len() != other.len() {
+            Tolerance::Never
+        } else {
+            max_tolerance(self.iter().zip(other.iter()).map(|(x, y)| x.required_tolerance(y)))
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B: PartialOrd, C: RequiredTolerance<A, B>> RequiredTolerance<Vec<A>, B> for Vec<C> {
+    fn required_tolerance(&self, other: &Vec<A>) -> Tolerance<B> {
+        self[..].required_tolerance(&other[..])
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B: PartialOrd, C: RequiredTolerance<A, B>, const N: usize> RequiredTolerance<[A; N], B> for [C; N]
+where
+    [C; N]: NearlyEq<[A; N], B>,
+{
+    fn required_tolerance(&self, other: &[A; N]) -> Tolerance<B> {
+        self[..].required_tolerance(&other[..])
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A: ?Sized, B, C: RequiredTolerance<A, B> + ?Sized> RequiredTolerance<A, B> for &C {
+    fn required_tolerance(&self, other: &A) -> Tolerance<B> {
+        (**self).required_tolerance(other)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A: ?Sized, B, C: RequiredTolerance<A, B> + ?Sized> RequiredTolerance<A, B> for &mut C {
+    fn required_tolerance(&self, other: &A) -> Tolerance<B> {
+        (**self).required_tolerance(other)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B, C: RequiredTolerance<A, B>> RequiredTolerance<Option<A>, B> for Option<C> {
+    fn required_tolerance(&self, other: &Option<A>) -> Tolerance<B> {
+        match (self, other) {
+            (None, None) => Tolerance::Any,
+            (None, _) | (_, None) => Tolerance::Never,
+            (Some(x), Some(y)) => x.required_tolerance(y),
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B, C: RequiredTolerance<A, B>> RequiredTolerance<Rc<A>, B> for Rc<C> {
+    fn required_tolerance(&self, other: &Rc<A>) -> Tolerance<B> {
+        self.as_ref().required_tolerance(other)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B, C: RequiredTolerance<A, B>> RequiredTolerance<Arc<A>, B> for Arc<C> {
+    fn required_tolerance(&self, other: &Arc<A>) -> Tolerance<B> {
+        self.as_ref().required_tolerance(other)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A, B, C: RequiredTolerance<A, B>> RequiredTolerance<Weak<A>, B> for Weak<C> {
+    fn required_tolerance(&self, other: &Weak<A>) -> Tolerance<B> {
+        self.upgrade().required_tolerance(&other.upgrade())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A: Copy, B, C: RequiredTolerance<A, B> + Copy> RequiredTolerance<Cell<A>, B> for Cell<C> {
+    fn required_tolerance(&self, other: &Cell<A>) -> Tolerance<B> {
+        self.get().required_tolerance(&other.get())
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<A: ?Sized, B, C: RequiredTolerance<A, B> + ?Sized> RequiredTolerance<RefCell<A>, B> for RefCell<C> {
+    fn required_tolerance(&self, other: &RefCell<A>) -> Tolerance<B> {
+        self.borrow().required_tolerance(&other.borrow())
+    }
+}
@@ -0,0 +1,265 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use {f32_ulps, f64_ulps, margin_ratio, max_margin, DefaultTolerance, NearlyEq};
+
+/// Trait for tolerances encoded in a type, used as the diff value of `Tolerant`.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait TolerancePolicy<T: ?Sized>: Copy + Default {
+    /// This method tests for the values to be nearly(approximately) equal under this tolerance.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn accepts(&self, a: &T, b: &T) -> bool;
+
+    /// This method returns the difference between the values as a ratio of this tolerance,
+    /// as `NearlyEq::margin` does.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn margin_of(&self, _a: &T, _b: &T) -> Option<f64> {
+        None
+    }
+}
+
+/// An absolute tolerance of `MANTISSA * 10^EXPONENT` for values of type `T`.
+///
+/// For example, `AbsTol<f64, 1, -9>` is an absolute tolerance of `1e-9`.
+/// Tolerance types are used through `Tolerant`, or directly with `TolerancePolicy`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use nearly_eq::{AbsTol, TolerancePolicy, Ulps};
+/// assert!(AbsTol::<f64, 1, -3>::default().accepts(&1.0, &1.0005));
+/// assert!(Ulps::<1>.accepts(&1.0f32, &(1.0 + f32::EPSILON)));
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct AbsTol<T, const MANTISSA: i64, const EXPONENT: i32>(PhantomData<T>);
+
+/// A tolerance of `N` units in the last place, the number of representable values between two floats.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Ulps<const N: u64>;
+
+impl<T, const MANTISSA: i64, const EXPONENT: i32> Clone for AbsTol<T, MANTISSA, EXPONENT> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const MANTISSA: i64, const EXPONENT: i32> Copy for AbsTol<T, MANTISSA, EXPONENT> {}
+
+impl<T, const MANTISSA: i64, const EXPONENT: i32> Default for AbsTol<T, MANTISSA, EXPONENT> {
+    fn default() -> Self {
+        AbsTol(PhantomData)
+    }
+}
+
+impl<T, const MANTISSA: i64, const EXPONENT: i32> fmt::Debug for AbsTol<T, MANTISSA, EXPONENT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AbsTol({}e{})", MANTISSA, EXPONENT)
+    }
+}
+
+macro_rules! float_impls {
+    ($($T:ident $ulps:ident)+) => {
+        $(
+            impl<const MANTISSA: i64, const EXPONENT: i32> AbsTol<$T, MANTISSA, EXPONENT> {
+                /// Returns the tolerance value.
+                #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+                pub fn value() -> $T {
+                    // Powers of ten up to `10^22` for `f64` and `10^10` for `f32` are exact, so with such
+                    // an exponent and an exact mantissa this rounds once: `AbsTol<f64, 1, -9>` is the
+                    // `f64` nearest to `1e-9`. Larger exponents may be off by a unit in the last place.
+                    if EXPONENT < 0 {
+                        MANTISSA as $T / (10 as $T).powi(-EXPONENT)
+                    } else {
+                        MANTISSA as $T * (10 as $T).powi(EXPONENT)
+                    }
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl<const MANTISSA: i64, const EXPONENT: i32> TolerancePolicy<$T> for AbsTol<$T, MANTISSA, EXPONENT> {
+                fn accepts(&self, a: &$T, b: &$T) -> bool {
+                    NearlyEq::eq(a, b, &Self::value())
+                }
+
+                fn margin_of(&self, a: &$T, b: &$T) -> Option<f64> {
+                    NearlyEq::margin(a, b, &Self::value())
+                }
+            }
+
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl<const N: u64> TolerancePolicy<$T> for Ulps<N> {
+                fn accepts(&self, a: &$T, b: &$T) -> bool {
+                    *a == *b || !(a.is_nan() || b.is_nan()) && $ulps(*a, *b) <= N
+                }
+
+                fn margin_of(&self, a: &$T, b: &$T) -> Option<f64> {
+                    if *a == *b {
+                        Some(0.0)
+                    } else if a.is_nan() || b.is_nan() {
+                        None
+                    } else {
+                        margin_ratio($ulps(*a, *b) as f64, N as f64)
+                    }
+                }
+            }
+        )+
+    }
+}
+
+float_impls! { f32 f32_ulps f64 f64_ulps }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P: TolerancePolicy<T>> TolerancePolicy<[T]> for P {
+    fn accepts(&self, a: &[T], b: &[T]) -> bool {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(x, y)| self.accepts(x, y))
+    }
+
+    fn margin_of(&self, a: &[T], b: &[T]) -> Option<f64> {
+        if a.len() != b.len() {
+            None
+        } else {
+            max_margin(a.iter().zip(b.iter()).map(|(x, y)| self.margin_of(x, y)))
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P: TolerancePolicy<T>> TolerancePolicy<Vec<T>> for P {
+    fn accepts(&self, a: &Vec<T>, b: &Vec<T>) -> bool {
+        TolerancePolicy::<[T]>::accepts(self, a, b)
+    }
+
+    fn margin_of(&self, a: &Vec<T>, b: &Vec<T>) -> Option<f64> {
+        TolerancePolicy::<[T]>::margin_of(self, a, b)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P: TolerancePolicy<T>, const N: usize> TolerancePolicy<[T; N]> for P {
+    fn accepts(&self, a: &[T; N], b: &[T; N]) -> bool {
+        TolerancePolicy::<[T]>::accepts(self, a, b)
+    }
+
+    fn margin_of(&self, a: &[T; N], b: &[T; N]) -> Option<f64> {
+        TolerancePolicy::<[T]>::margin_of(self, a, b)
+    }
+}
+
+/// Wrapper that carries its tolerance in its type.
+///
+/// `DefaultTolerance::default_tolerance()` returns the tolerance `P`, and `PartialEq` compares under it,
+/// so structs holding `Tolerant` fields carry their comparison policy.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::{AbsTol, Tolerant, Ulps};
+/// # fn main() {
+/// type Coarse = AbsTol<f64, 1, -3>;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Reading {
+///     level: Tolerant<f64, Coarse>,
+///     gain: Tolerant<f32, Ulps<4>>,
+/// }
+///
+/// assert_eq!(
+///     Reading { level: Tolerant::new(1.0), gain: Tolerant::new(0.5) },
+///     Reading { level: Tolerant::new(1.0005), gain: Tolerant::new(0.5 + f32::EPSILON) }
+/// );
+/// assert_nearly_eq!(Tolerant::<f64, Coarse>::new(1.0), Tolerant::new(1.0005)); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::{AbsTol, Tolerant};
+/// # fn main() {
+/// assert_nearly_eq!(Tolerant::<f64, AbsTol<f64, 1, -9>>::new(1.0), Tolerant::new(1.0005)); // panics
+/// # }
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Tolerant<T, P> {
+    /// The wrapped value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub value: T,
+    policy: PhantomData<P>,
+}
+
+impl<T, P> Tolerant<T, P> {
+    /// Wraps the value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(value: T) -> Tolerant<T, P> {
+        Tolerant {
+            value,
+            policy: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped value.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Clone, P> Clone for Tolerant<T, P> {
+    fn clone(&self) -> Self {
+        Tolerant::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, P> Copy for Tolerant<T, P> {}
+
+impl<T: Default, P> Default for Tolerant<T, P> {
+    fn default() -> Self {
+        Tolerant::new(T::default())
+    }
+}
+
+impl<T, P> Deref for Tolerant<T, P> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Debug, P> fmt::Debug for Tolerant<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P: TolerancePolicy<T>> NearlyEq<Tolerant<T, P>, P> for Tolerant<T, P> {
+    fn eq(&self, other: &Tolerant<T, P>, eps: &P) -> bool {
+        eps.accepts(&self.value, &other.value)
+    }
+
+    fn margin(&self, other: &Tolerant<T, P>, eps: &P) -> Option<f64> {
+        eps.margin_of(&self.value, &other.value)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P: TolerancePolicy<T>> DefaultTolerance<P> for Tolerant<T, P> {
+    fn default_tolerance() -> P {
+        P::default()
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<T, P: TolerancePolicy<T>> PartialEq for Tolerant<T, P> {
+    fn eq(&self, other: &Tolerant<T, P>) -> bool {
+        P::default().accepts(&self.value, &other.value)
+    }
+}
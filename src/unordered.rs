@@ -0,0 +1,221 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use {DefaultTolerance, NearlyEq, Sequence};
+
+/// Compares collections as multisets, ignoring the order of the elements.
+///
+/// The values are nearly equal if every left element can be paired with a distinct right element
+/// it is nearly equal to. The pairing is a maximum bipartite matching, so near-ties that a
+/// sort-then-compare would split are still matched.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Unordered;
+/// # fn main() {
+/// let roots = vec![2f64, -1.0, 0.5];
+/// assert_nearly_eq!(Unordered::new(&roots), vec![-1f64, 0.5, 2.0]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::Unordered;
+/// # fn main() {
+/// let roots = vec![2f64, -1.0, 0.5];
+/// assert_nearly_eq!(Unordered::new(&roots), vec![-1f64, 0.5, 0.5]); // panics, reporting the unmatched elements
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct Unordered<'a, L: ?Sized + 'a> {
+    values: &'a L,
+}
+
+impl<'a, L: Sequence + ?Sized> Unordered<'a, L> {
+    /// Compares the values ignoring their order.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(values: &'a L) -> Unordered<'a, L> {
+        Unordered { values }
+    }
+
+    /// Returns the index of the right element matched to each left element,
+    /// or `None` for left elements without a match.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn matching<R, B>(&self, other: &R, eps: &B) -> Vec<Option<usize>>
+    where
+        R: Sequence + ?Sized,
+        L::Item: NearlyEq<R::Item, B>,
+    {
+        let left: Vec<&L::Item> = self.values.elements().collect();
+        let right: Vec<&R::Item> = other.elements().collect();
+        let mut matched_left: Vec<Option<usize>> = vec![None; right.len()];
+        // Pair elements at the same index first, so that ordered values need no search.
+        let mut unmatched = Vec::new();
+        for (i, x) in left.iter().enumerate() {
+            match right.get(i) {
+                Some(y) if x.eq(*y, eps) => matched_left[i] = Some(i),
+                _ => unmatched.push(i),
+            }
+        }
+        let mut candidates = Candidates {
+            lists: vec![None; left.len()],
+            edges: |i: usize| {
+                right
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, y)| left[i].eq(*y, eps))
+                    .map(|(j, _)| j)
+                    .collect()
+            },
+        };
+        let mut visited = vec![usize::MAX; right.len()];
+        for i in unmatched {
+            augment(i, &mut candidates, &mut matched_left, &mut visited);
+        }
+        let mut matching = vec![None; left.len()];
+        for (j, i) in matched_left.into_iter().enumerate() {
+            if let Some(i) = i {
+                matching[i] = Some(j);
+            }
+        }
+        matching
+    }
+
+    fn unmatched<R, B>(&self, other: &R, eps: &B) -> (Vec<usize>, Vec<usize>)
+    where
+        R: Sequence + ?Sized,
+        L::Item: NearlyEq<R::Item, B>,
+    {
+        let matching = self.matching(other, eps);
+        let mut right_matched = vec![false; other.len()];
+        for &j in matching.iter().flatten() {
+            right_matched[j] = true;
+        }
+        (
+            matching
+                .iter()
+                .enumerate()
+                .filter(|&(_, j)| j.is_none())
+                .map(|(i, _)| i)
+                .collect(),
+            right_matched
+                .iter()
+                .enumerate()
+                .filter(|&(_, &matched)| !matched)
+                .map(|(j, _)| j)
+                .collect(),
+        )
+    }
+}
+
+// The right elements nearly equal to each left element, computed when first needed.
+struct Candidates<F> {
+    lists: Vec<Option<Vec<usize>>>,
+    edges: F,
+}
+
+impl<F: FnMut(usize) -> Vec<usize>> Candidates<F> {
+    fn get(&mut self, i: usize, n: usize) -> Option<usize> {
+        if self.lists[i].is_none() {
+            self.lists[i] = Some((self.edges)(i));
+        }
+        self.lists[i].as_ref().and_then(|list| list.get(n).cloned())
+    }
+}
+
+// Kuhn's augmenting path search for left element `root`, with an explicit stack.
+// `visited[j] == root` marks the right elements already tried in this search.
+fn augment<F: FnMut(usize) -> Vec<usize>>(
+    root: usize,
+    candidates: &mut Candidates<F>,
+    matched_left: &mut [Option<usize>],
+    visited: &mut [usize],
+) -> bool {
+    // Each frame is a left element and the position of its next candidate;
+    // `path[k]` is the right element leading from frame `k` to frame `k + 1`.
+    let mut stack = vec![(root, 0)];
+    let mut path: Vec<usize> = Vec::new();
+    while let Some(&mut (i, ref mut n)) = stack.last_mut() {
+        let j = match candidates.get(i, *n) {
+            Some(j) => j,
+            None => {
+                stack.pop();
+                path.pop();
+                continue;
+            }
+        };
+        *n += 1;
+        if visited[j] == root {
+            continue;
+        }
+        visited[j] = root;
+        match matched_left[j] {
+            Some(k) => {
+                path.push(j);
+                stack.push((k, 0));
+            }
+            None => {
+                matched_left[j] = Some(i);
+                for (&(i, _), &j) in stack.iter().zip(path.iter()) {
+                    matched_left[j] = Some(i);
+                }
+                return true;
+            }
+        }
+    }
+    false
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for Unordered<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L, R> NearlyEq<R, B> for Unordered<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence + ?Sized,
+    L::Item: NearlyEq<R::Item, B>,
+{
+    fn eq(&self, other: &R, eps: &B) -> bool {
+        self.values.len() == other.len() && self.matching(other, eps).iter().all(Option::is_some)
+    }
+
+    fn explain(&self, other: &R, eps: &B) -> Option<String> {
+        let (left, right) = self.unmatched(other, eps);
+        let mut message = format!(
+            "{} left element(s) unmatched at index {:?}, {} right element(s) unmatched at index {:?}",
+            left.len(),
+            left,
+            right.len(),
+            right
+        );
+        if self.values.len() != other.len() {
+            message.push_str(&format!(
+                ", lengths differ: {} and {}",
+                self.values.len(),
+                other.len()
+            ));
+        }
+        Some(message)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L> DefaultTolerance<B> for Unordered<'a, L>
+where
+    L: Sequence + ?Sized,
+    L::Item: DefaultTolerance<B>,
+{
+    fn default_tolerance() -> B {
+        L::Item::default_tolerance()
+    }
+}
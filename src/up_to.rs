@@ -0,0 +1,202 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use {max_margin, ComparisonStats, DefaultTolerance, NearlyEq, Sequence};
+
+/// The kinds of scalar factor `UpTo` fits before comparing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub enum Factor {
+    /// A factor of `1` or `-1`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Sign,
+    /// A factor of unit modulus, the same as `Sign` for real values.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Phase,
+    /// Any nonzero factor. A fit of zero or a non-finite fit falls back to `1`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Scale,
+}
+
+/// Trait for element types that can be multiplied by a best-fitting scalar factor.
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait Scalable: Sized {
+    /// This method returns the factor `s` of the given kind for which `left ≈ s * right`
+    /// in the least squares sense.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn fit<'a, I>(pairs: I, factor: Factor) -> Self
+    where
+        Self: 'a,
+        I: IntoIterator<Item = (&'a Self, &'a Self)>;
+
+    /// This method returns the value multiplied by the factor.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn scale_by(&self, factor: &Self) -> Self;
+}
+
+macro_rules! float_impls {
+    ($($T:ident)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl Scalable for $T {
+                fn fit<'a, I>(pairs: I, factor: Factor) -> $T
+                where
+                    I: IntoIterator<Item = (&'a $T, &'a $T)>,
+                {
+                    let (dot, norm_sqr) = pairs
+                        .into_iter()
+                        .fold((0.0, 0.0), |(dot, norm_sqr), (x, y)| (dot + x * y, norm_sqr + y * y));
+                    match factor {
+                        Factor::Sign | Factor::Phase => if dot < 0.0 { -1.0 } else { 1.0 },
+                        Factor::Scale => {
+                            let scale = dot / norm_sqr;
+                            if scale == 0.0 || !scale.is_finite() { 1.0 } else { scale }
+                        }
+                    }
+                }
+
+                fn scale_by(&self, factor: &$T) -> $T {
+                    *self * *factor
+                }
+            }
+        )+
+    }
+}
+
+float_impls! { f32 f64 }
+
+/// Compares collections up to a scalar factor of the right values.
+///
+/// The best-fitting factor `s` of the requested kind is found first, then each left element
+/// is compared with `s` times the right element using its `NearlyEq` implementation.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::UpTo;
+/// # fn main() {
+/// let eigenvector = vec![0.6f64, -0.8];
+/// assert_nearly_eq!(UpTo::sign(&eigenvector), vec![-0.6f64, 0.8]); // does not panic
+/// assert_nearly_eq!(UpTo::scale(&eigenvector), vec![3f64, -4.0]); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::UpTo;
+/// # fn main() {
+/// let eigenvector = vec![0.6f64, -0.8];
+/// assert_nearly_eq!(UpTo::sign(&eigenvector), vec![3f64, -4.0]); // panics
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct UpTo<'a, L: ?Sized + 'a> {
+    values: &'a L,
+    factor: Factor,
+}
+
+impl<'a, L: Sequence + ?Sized> UpTo<'a, L>
+where
+    L::Item: Scalable,
+{
+    /// Compares up to the given kind of factor.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn new(values: &'a L, factor: Factor) -> UpTo<'a, L> {
+        UpTo { values, factor }
+    }
+
+    /// Compares up to sign.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn sign(values: &'a L) -> UpTo<'a, L> {
+        UpTo::new(values, Factor::Sign)
+    }
+
+    /// Compares up to a unit-modulus phase.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn phase(values: &'a L) -> UpTo<'a, L> {
+        UpTo::new(values, Factor::Phase)
+    }
+
+    /// Compares up to any nonzero scale.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn scale(values: &'a L) -> UpTo<'a, L> {
+        UpTo::new(values, Factor::Scale)
+    }
+
+    /// Returns the best-fitting factor, or `None` if the lengths differ.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn fitted<R: Sequence<Item = L::Item> + ?Sized>(&self, other: &R) -> Option<L::Item> {
+        if self.values.len() != other.len() {
+            None
+        } else {
+            Some(L::Item::fit(
+                self.values.elements().zip(other.elements()),
+                self.factor,
+            ))
+        }
+    }
+
+    fn scaled<R: Sequence<Item = L::Item> + ?Sized>(&self, other: &R) -> Option<Vec<L::Item>> {
+        let factor = self.fitted(other)?;
+        Some(other.elements().map(|y| y.scale_by(&factor)).collect())
+    }
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for UpTo<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L, R> NearlyEq<R, B> for UpTo<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence<Item = L::Item> + ?Sized,
+    L::Item: Scalable + NearlyEq<L::Item, B> + fmt::Debug,
+{
+    fn eq(&self, other: &R, eps: &B) -> bool {
+        match self.scaled(other) {
+            Some(scaled) => self.values.elements().zip(scaled.iter()).all(|(x, y)| x.eq(y, eps)),
+            None => false,
+        }
+    }
+
+    fn margin(&self, other: &R, eps: &B) -> Option<f64> {
+        let scaled = self.scaled(other)?;
+        max_margin(self.values.elements().zip(scaled.iter()).map(|(x, y)| x.margin(y, eps)))
+    }
+
+    fn stats(&self, other: &R, eps: &B) -> Option<ComparisonStats> {
+        let scaled = self.scaled(other)?;
+        Some(ComparisonStats::from_pairs(
+            self.values.elements().zip(scaled.iter()),
+            eps,
+        ))
+    }
+
+    fn explain(&self, other: &R, eps: &B) -> Option<String> {
+        let factor = self.fitted(other)?;
+        let mut message = format!("best-fitting {:?} factor {:?}", self.factor, factor);
+        if let Some(stats) = self.stats(other, eps) {
+            message.push_str(&format!("\n{}", stats));
+        }
+        Some(message)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L> DefaultTolerance<B> for UpTo<'a, L>
+where
+    L: Sequence + ?Sized,
+    L::Item: DefaultTolerance<B>,
+{
+    fn default_tolerance() -> B {
+        L::Item::default_tolerance()
+    }
+}
@@ -0,0 +1,225 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use {max_deviation, max_margin, Deviation, NearlyEq};
+
+/// The values `NearlyZero` tests against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub enum Target {
+    /// Zero, tested by `is_nearly_zero`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Zero,
+    /// The nearest integer, tested by `is_nearly_integer`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    Integer,
+    /// One, tested by `is_nearly_one`.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    One,
+}
+
+/// Trait for testing values against zero, the nearest integer and one.
+///
+/// Collections test each of their elements.
+///
+/// # Examples
+///
+/// ```rust
+/// # use nearly_eq::NearlyZero;
+/// assert!(1e-13f64.is_nearly_zero(&1e-12));
+/// assert!(2.9999999f32.is_nearly_integer(&1e-6));
+/// assert!(vec![1f64, 1.0 + 1e-13].is_nearly_one(&1e-12));
+/// ```
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub trait NearlyZero<Diff = Self>: NearlyEq<Self, Diff> {
+    /// This method tests for the value to be nearly(approximately) equal to zero.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn is_nearly_zero(&self, eps: &Diff) -> bool;
+
+    /// This method tests for the value to be nearly(approximately) equal to the nearest integer.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn is_nearly_integer(&self, eps: &Diff) -> bool;
+
+    /// This method tests for the value to be nearly(approximately) equal to one.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn is_nearly_one(&self, eps: &Diff) -> bool;
+
+    /// This method returns the difference from the target as a ratio of the diff value, as `NearlyEq::margin` does.
+    ///
+    /// Returns `None` by default.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn margin_from(&self, target: Target, eps: &Diff) -> Option<f64> {
+        let _ = (target, eps);
+        None
+    }
+
+    /// This method returns the difference from the target, as `NearlyEq::deviation` does.
+    ///
+    /// Returns `None` by default.
+    #[inline]
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    fn deviation_from(&self, target: Target) -> Option<Deviation> {
+        let _ = target;
+        None
+    }
+}
+
+macro_rules! float_impls {
+    ($($T:ident)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl NearlyZero for $T {
+                fn is_nearly_zero(&self, eps: &$T) -> bool {
+                    NearlyEq::eq(self, &0.0, eps)
+                }
+
+                fn is_nearly_integer(&self, eps: &$T) -> bool {
+                    self.is_finite() && NearlyEq::eq(self, &self.round(), eps)
+                }
+
+                fn is_nearly_one(&self, eps: &$T) -> bool {
+                    NearlyEq::eq(self, &1.0, eps)
+                }
+
+                fn margin_from(&self, target: Target, eps: &$T) -> Option<f64> {
+                    let target = match target {
+                        Target::Zero => 0.0,
+                        Target::Integer => self.round(),
+                        Target::One => 1.0,
+                    };
+                    NearlyEq::margin(self, &target, eps)
+                }
+
+                fn deviation_from(&self, target: Target) -> Option<Deviation> {
+                    let target = match target {
+                        Target::Zero => 0.0,
+                        Target::Integer => self.round(),
+                        Target::One => 1.0,
+                    };
+                    NearlyEq::<$T, $T>::deviation(self, &target)
+                }
+            }
+        )+
+    }
+}
+
+float_impls! { f32 f64 }
+
+macro_rules! int_impls {
+    ($($T:ident)+) => {
+        $(
+            #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+            impl NearlyZero for $T {
+                fn is_nearly_zero(&self, eps: &$T) -> bool {
+                    NearlyEq::eq(self, &0, eps)
+                }
+
+                fn is_nearly_integer(&self, _eps: &$T) -> bool {
+                    true
+                }
+
+                fn is_nearly_one(&self, eps: &$T) -> bool {
+                    NearlyEq::eq(self, &1, eps)
+                }
+
+                fn margin_from(&self, target: Target, eps: &$T) -> Option<f64> {
+                    let target = match target {
+                        Target::Zero => 0,
+                        Target::Integer => *self,
+                        Target::One => 1,
+                    };
+                    NearlyEq::margin(self, &target, eps)
+                }
+
+                fn deviation_from(&self, target: Target) -> Option<Deviation> {
+                    let target = match target {
+                        Target::Zero => 0,
+                        Target::Integer => *self,
+                        Target::One => 1,
+                    };
+                    NearlyEq::deviation(self, &target)
+                }
+            }
+        )+
+    }
+}
+
+int_impls! { i8 i16 i32 i64 u8 u16 u32 u64 }
+
+#[cfg(feature = "i128")]
+int_impls! { i128 u128 }
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: NearlyZero<B>> NearlyZero<B> for [C] {
+    fn is_nearly_zero(&self, eps: &B) -> bool {
+        self.iter().all(|x| x.is_nearly_zero(eps))
+    }
+
+    fn is_nearly_integer(&self, eps: &B) -> bool {
+        self.iter().all(|x| x.is_nearly_integer(eps))
+    }
+
+    fn is_nearly_one(&self, eps: &B) -> bool {
+        self.iter().all(|x| x.is_nearly_one(eps))
+    }
+
+    fn margin_from(&self, target: Target, eps: &B) -> Option<f64> {
+        max_margin(self.iter().map(|x| x.margin_from(target, eps)))
+    }
+
+    fn deviation_from(&self, target: Target) -> Option<Deviation> {
+        max_deviation(self.iter().map(|x| x.deviation_from(target)))
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: NearlyZero<B>> NearlyZero<B> for Vec<C> {
+    fn is_nearly_zero(&self, eps: &B) -> bool {
+        self[..].is_nearly_zero(eps)
+    }
+
+    fn is_nearly_integer(&self, eps: &B) -> bool {
+        self[..].is_nearly_integer(eps)
+    }
+
+    fn is_nearly_one(&self, eps: &B) -> bool {
+        self[..].is_nearly_one(eps)
+    }
+
+    fn margin_from(&self, target: Target, eps: &B) -> Option<f64> {
+        self[..].margin_from(target, eps)
+    }
+
+    fn deviation_from(&self, target: Target) -> Option<Deviation> {
+        self[..].deviation_from(target)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<B, C: NearlyZero<B>, const N: usize> NearlyZero<B> for [C; N]
+where
+    [C; N]: NearlyEq<[C; N], B>,
+{
+    fn is_nearly_zero(&self, eps: &B) -> bool {
+        self[..].is_nearly_zero(eps)
+    }
+
+    fn is_nearly_integer(&self, eps: &B) -> bool {
+        self[..].is_nearly_integer(eps)
+    }
+
+    fn is_nearly_one(&self, eps: &B) -> bool {
+        self[..].is_nearly_one(eps)
+    }
+
+    fn margin_from(&self, target: Target, eps: &B) -> Option<f64> {
+        self[..].margin_from(target, eps)
+    }
+
+    fn deviation_from(&self, target: Target) -> Option<Deviation> {
+        self[..].deviation_from(target)
+    }
+}
@@ -0,0 +1,176 @@
+//! # Licensing
+//! This Source Code is subject to the terms of the Mozilla Public License
+//! version 2.0 (the "License"). You can obtain a copy of the License at
+//! [http://mozilla.org/MPL/2.0/](http://mozilla.org/MPL/2.0/).
+
+use std::fmt;
+
+use {max_margin, DefaultTolerance, NearlyEq, Sequence};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Leading,
+    Trailing,
+}
+
+type Pair<'b, A, B> = (usize, Option<&'b A>, Option<&'b B>);
+
+/// Compares collections of different lengths elementwise, treating missing elements as zero.
+///
+/// The shorter collection is padded with `Default::default()` values, at the end for
+/// `trailing` and at the start for `leading`, and the padded elements must be nearly equal to zero.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::ZeroPadded;
+/// # fn main() {
+/// let coefficients = vec![1f64, 2.0, 3.0];
+/// assert_nearly_eq!(ZeroPadded::trailing(&coefficients), vec![1f64, 2.0, 3.0, 1e-9, 0.0], 1e-6); // does not panic
+/// assert_nearly_eq!(ZeroPadded::leading(&coefficients), vec![0f64, 1.0, 2.0, 3.0], 1e-6); // does not panic
+/// # }
+/// ```
+/// ```should_panic
+/// # #[macro_use] extern crate nearly_eq;
+/// # use nearly_eq::ZeroPadded;
+/// # fn main() {
+/// let coefficients = vec![1f64, 2.0, 3.0];
+/// assert_nearly_eq!(ZeroPadded::trailing(&coefficients), vec![1f64, 2.0, 3.0, 0.5], 1e-6); // panics, reporting index 3 of the right values
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+pub struct ZeroPadded<'a, L: ?Sized + 'a> {
+    values: &'a L,
+    side: Side,
+}
+
+impl<'a, L: Sequence + ?Sized> ZeroPadded<'a, L> {
+    /// Pads the shorter collection with zeros at the end.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn trailing(values: &'a L) -> ZeroPadded<'a, L> {
+        ZeroPadded {
+            values,
+            side: Side::Trailing,
+        }
+    }
+
+    /// Pads the shorter collection with zeros at the start.
+    #[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+    pub fn leading(values: &'a L) -> ZeroPadded<'a, L> {
+        ZeroPadded {
+            values,
+            side: Side::Leading,
+        }
+    }
+
+    fn pairs<'b, R: Sequence + ?Sized>(
+        &'b self,
+        other: &'b R,
+    ) -> Vec<Pair<'b, L::Item, R::Item>> {
+        let len = self.values.len().max(other.len());
+        let left = padded(self.values.elements(), len - self.values.len(), self.side);
+        let right = padded(other.elements(), len - other.len(), self.side);
+        left.zip(right)
+            .enumerate()
+            .map(|(i, (x, y))| (i, x, y))
+            .collect()
+    }
+}
+
+fn padded<'b, T>(
+    elements: Box<dyn Iterator<Item = &'b T> + 'b>,
+    padding: usize,
+    side: Side,
+) -> Box<dyn Iterator<Item = Option<&'b T>> + 'b> {
+    let zeros = (0..padding).map(|_| None);
+    match side {
+        Side::Leading => Box::new(zeros.chain(elements.map(Some))),
+        Side::Trailing => Box::new(elements.map(Some).chain(zeros)),
+    }
+}
+
+impl<'a, L: fmt::Debug + ?Sized> fmt::Debug for ZeroPadded<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L, R> NearlyEq<R, B> for ZeroPadded<'a, L>
+where
+    L: Sequence + ?Sized,
+    R: Sequence + ?Sized,
+    L::Item: NearlyEq<R::Item, B> + Default,
+    R::Item: Default,
+{
+    fn eq(&self, other: &R, eps: &B) -> bool {
+        let (left_zero, right_zero) = (L::Item::default(), R::Item::default());
+        self.pairs(other).into_iter().all(|(_, x, y)| {
+            x.unwrap_or(&left_zero).eq(y.unwrap_or(&right_zero), eps)
+        })
+    }
+
+    fn margin(&self, other: &R, eps: &B) -> Option<f64> {
+        let (left_zero, right_zero) = (L::Item::default(), R::Item::default());
+        max_margin(
+            self.pairs(other)
+                .into_iter()
+                .map(|(_, x, y)| x.unwrap_or(&left_zero).margin(y.unwrap_or(&right_zero), eps)),
+        )
+    }
+
+    fn explain(&self, other: &R, eps: &B) -> Option<String> {
+        let (left_zero, right_zero) = (L::Item::default(), R::Item::default());
+        let mut mismatches = Vec::new();
+        let mut left_extra = Vec::new();
+        let mut right_extra = Vec::new();
+        for (i, x, y) in self.pairs(other) {
+            match (x, y) {
+                (Some(x), Some(y)) if x.ne(y, eps) => mismatches.push(i),
+                (Some(x), None) if x.ne(&right_zero, eps) => left_extra.push(i),
+                (None, Some(y)) if left_zero.ne(y, eps) => right_extra.push(i),
+                _ => {}
+            }
+        }
+        let mut messages = Vec::new();
+        if !mismatches.is_empty() {
+            messages.push(format!(
+                "{} mismatch(es) at index {:?}",
+                mismatches.len(),
+                mismatches
+            ));
+        }
+        if !left_extra.is_empty() {
+            messages.push(format!(
+                "left has {} extra non-zero element(s) at index {:?}",
+                left_extra.len(),
+                left_extra
+            ));
+        }
+        if !right_extra.is_empty() {
+            messages.push(format!(
+                "right has {} extra non-zero element(s) at index {:?}",
+                right_extra.len(),
+                right_extra
+            ));
+        }
+        if messages.is_empty() {
+            None
+        } else {
+            Some(messages.join(", "))
+        }
+    }
+}
+
+#[cfg_attr(feature = "docs", stable(feature = "default", since = "0.3.0"))]
+impl<'a, B, L> DefaultTolerance<B> for ZeroPadded<'a, L>
+where
+    L: Sequence + ?Sized,
+    L::Item: DefaultTolerance<B>,
+{
+    fn default_tolerance() -> B {
+        L::Item::default_tolerance()
+    }
+}
@@ -0,0 +1,33 @@
+#[macro_use]
+extern crate nearly_eq;
+
+struct Ignore;
+
+impl nearly_eq::Reporter for Ignore {
+    fn report(&self, _: &nearly_eq::Comparison) {}
+}
+
+// Calibration is process-global, so it is tested in a single test of its own binary.
+#[test]
+fn calibration_records_largest_difference_per_call_site() {
+    let path = std::env::temp_dir().join(format!("nearly_eq_{}_calibration.tsv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    std::env::set_var("NEARLY_EQ_CALIBRATE", &path);
+    nearly_eq::set_reporter(Box::new(Ignore));
+    nearly_eq::take_reporter();
+    for x in &[1.25f64, 1.5, 1.125] {
+        assert_nearly_eq!(1f64, *x, 1f64);
+    }
+    nearly_eq::disable_calibration();
+    assert_nearly_eq!(1f64, 2f64, 2f64);
+    let records = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(records.lines().count(), 3);
+    let sites = nearly_eq::read_calibration(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(sites.len(), 1);
+    assert_eq!(sites[0].file, "tests/calibrate.rs");
+    assert_eq!(sites[0].count, 3);
+    assert_eq!(sites[0].deviation.abs, 0.5);
+    assert_eq!(sites[0].suggest(2.0).abs, 1.0);
+    assert_eq!(sites[0].suggest(2.0).ulps, Some(1 << 52));
+}
@@ -1,3 +1,4 @@
+
 #[cfg(feature = "num-complex")]
 extern crate num_complex;
 
@@ -10,6 +11,13 @@ extern crate num_rational;
 #[cfg(feature = "fpa")]
 extern crate fpa;
 
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+
+#[cfg(feature = "approx")]
+#[macro_use]
+extern crate approx;
+
 #[macro_use]
 extern crate nearly_eq;
 
@@ -17,8 +25,12 @@ extern crate nearly_eq;
 use num_complex::Complex;
 
 #[cfg(feature = "num-rational")]
+#[allow(deprecated)]
 use num_rational::Rational;
 
+#[cfg(feature = "num-rational")]
+use num_rational::Rational64;
+
 #[cfg(feature = "ndarray")]
 use ndarray::{ArrayD, IxDyn, arr1, arr2, arr3};
 
@@ -37,6 +49,7 @@ fn it_should_not_panic_if_values_are_nearly_equal() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_cast)]
 fn it_should_not_panic_if_values_are_nearly_equal_f64() {
     assert_nearly_eq!(0f64, 1e-12 as f64);
 }
@@ -276,6 +289,7 @@ fn bad_compare_with_option_right_some() {
 
 #[test]
 #[cfg(feature = "num-rational")]
+#[allow(deprecated)]
 fn compare_with_ratio_mindiff() {
     let left = Rational::new(1, 1000);
     let right = Rational::new(1, 1001);
@@ -285,6 +299,7 @@ fn compare_with_ratio_mindiff() {
 
 #[test]
 #[cfg(feature = "num-rational")]
+#[allow(deprecated)]
 fn compare_with_ratio_equal() {
     let left = Rational::new(1, 1000);
     let right = Rational::new(1, 1000);
@@ -294,6 +309,7 @@ fn compare_with_ratio_equal() {
 #[test]
 #[should_panic]
 #[cfg(feature = "num-rational")]
+#[allow(deprecated)]
 fn bad_compare_with_ratio() {
     let left = Rational::new(1, 1000);
     let right = Rational::new(1, 1001);
@@ -410,6 +426,7 @@ fn it_should_not_panic_if_values_are_nearly_equal_debug() {
 
 #[test]
 #[cfg(debug_assertions)]
+#[allow(clippy::unnecessary_cast)]
 fn it_should_not_panic_if_values_are_nearly_equal_f64_debug() {
     debug_assert_nearly_eq!(0f64, 1e-12 as f64);
 }
@@ -432,3 +449,1272 @@ fn compare_with_explicit_eps_debug() {
 fn it_should_panic_if_values_are_not_nearly_equal_debug() {
     debug_assert_nearly_eq!(8f32, 8f32 - 1e-5);
 }
+
+#[test]
+fn soft_asserts_should_not_panic_if_all_checks_pass() {
+    let mut s = nearly_eq::soft();
+    assert!(s.check(&0f64, &1e-12f64));
+    assert!(s.check_eps(&3f64, &4f64, &2f64));
+    s.finish();
+}
+
+#[test]
+fn soft_asserts_should_record_every_failure() {
+    let mut s = nearly_eq::soft();
+    assert!(!s.check(&1f64, &2f64));
+    assert!(s.check(&2f64, &2f64));
+    assert!(!s.check_eps(&3f64, &4f64, &1e-3f64));
+    assert_eq!(s.failures().len(), 2);
+    assert!(s.failures()[0].contains("lib.rs"));
+    assert!(s.failures()[1].contains("eps: `0.001`"));
+    std::mem::forget(s);
+}
+
+#[test]
+#[should_panic(expected = "2 nearly equality assertion(s) failed")]
+fn bad_soft_asserts_finish() {
+    let mut s = nearly_eq::soft();
+    s.check(&1f64, &2f64);
+    s.check(&vec![1f32, 2.0], &vec![1f32, 2.1]);
+    s.finish();
+}
+
+#[test]
+#[should_panic(expected = "1 nearly equality assertion(s) failed")]
+fn bad_soft_asserts_drop() {
+    let mut s = nearly_eq::soft();
+    s.check(&1f64, &2f64);
+}
+
+#[test]
+fn margin_of_scalars_and_collections() {
+    use nearly_eq::NearlyEq;
+    assert_eq!(NearlyEq::margin(&1f64, &1f64, &0.5), Some(0.0));
+    assert_eq!(NearlyEq::margin(&1f64, &1.25f64, &0.5), Some(0.5));
+    assert_eq!(NearlyEq::margin(&3i32, &1i32, &4), Some(0.5));
+    assert_eq!(NearlyEq::margin(&vec![1f64, 2.0], &vec![1.25, 2.0], &0.125), Some(2.0));
+    assert_eq!(NearlyEq::margin(&vec![1f64, 2.0], &vec![1.0], &0.125), None);
+    assert_eq!(NearlyEq::margin(&f64::NAN, &1f64, &0.5), None);
+}
+
+fn report_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("nearly_eq_{}_{}", std::process::id(), name))
+}
+
+fn comparison(passed: bool) -> nearly_eq::Comparison {
+    nearly_eq::Comparison {
+        passed,
+        assertion: "(left <= right)",
+        left: "1.0".to_string(),
+        right: "\"2\" <3>".to_string(),
+        eps: "0.5".to_string(),
+        margin: Some(2.0),
+        location: std::panic::Location::caller(),
+    }
+}
+
+#[test]
+fn json_lines_reporter_appends_comparisons() {
+    use nearly_eq::Reporter;
+    let path = report_path("report.jsonl");
+    let _ = std::fs::remove_file(&path);
+    let reporter = nearly_eq::JsonLinesReporter::new(&path);
+    assert!(!reporter.report_passed());
+    reporter.report(&comparison(false));
+    reporter.report(&comparison(true));
+    let content = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let lines: Vec<_> = content.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(
+        "{\"passed\":false,\"assertion\":\"(left <= right)\",\"left\":\"1.0\",\"right\":\"\\\"2\\\" <3>\""
+    ));
+    assert!(lines[0].contains("\"margin\":2,\"file\":\"tests/lib.rs\""));
+}
+
+#[test]
+fn junit_reporter_writes_test_suite() {
+    use nearly_eq::Reporter;
+    let path = report_path("report.xml");
+    let reporter = nearly_eq::JUnitReporter::new(&path).with_passed(true);
+    assert!(reporter.report_passed());
+    reporter.report(&comparison(true));
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("<testsuite name=\"nearly_eq\" tests=\"1\" failures=\"0\">"));
+    reporter.report(&comparison(false));
+    let content = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(content.contains("<testsuite name=\"nearly_eq\" tests=\"2\" failures=\"1\">"));
+    assert!(content.contains("<failure message=\"assertion failed: `(left &lt;= right)`\">"));
+    assert!(content.contains("right: &quot;2&quot; &lt;3&gt;"));
+    assert!(content.contains("margin: 2</failure>"));
+}
+
+#[test]
+fn global_reporter_receives_failed_comparisons() {
+    use nearly_eq::Monotonic;
+    use std::panic::catch_unwind;
+    use std::sync::{Arc, Mutex};
+
+    struct Collect(Arc<Mutex<Vec<nearly_eq::Comparison>>>);
+
+    impl nearly_eq::Reporter for Collect {
+        fn report(&self, comparison: &nearly_eq::Comparison) {
+            if comparison.eps == "0.0625" {
+                self.0.lock().unwrap().push(comparison.clone());
+            }
+        }
+    }
+
+    let comparisons = Arc::new(Mutex::new(Vec::new()));
+    nearly_eq::set_reporter(Box::new(Collect(comparisons.clone())));
+    assert_nearly_eq!(1f64, 1.03125f64, 0.0625f64);
+    assert!(catch_unwind(|| assert_nearly_eq!(1f64, 1.125f64, 0.0625f64)).is_err());
+    assert!(catch_unwind(|| assert_nearly_ge!(1f64, 1.125f64, 0.0625f64)).is_err());
+    assert!(catch_unwind(|| assert_nearly_zero!(vec![0f64, 0.125], 0.0625f64)).is_err());
+    assert!(catch_unwind(|| assert_nearly_integer!(2.125f64, 0.0625f64)).is_err());
+    assert!(catch_unwind(|| assert_nearly_one!(1.125f64, 0.0625f64)).is_err());
+    assert!(catch_unwind(|| assert_nearly_monotonic!([1f64, 0.875], Monotonic::Increasing, 0.0625f64)).is_err());
+    nearly_eq::take_reporter();
+    let comparisons = comparisons.lock().unwrap();
+    let assertions: Vec<_> = comparisons.iter().map(|c| c.assertion).collect();
+    assert_eq!(
+        assertions,
+        [
+            "(left == right)",
+            "(left >= right)",
+            "value is nearly zero",
+            "value is nearly an integer",
+            "value is nearly one",
+            "values are nearly increasing",
+        ]
+    );
+    assert!(comparisons.iter().all(|c| !c.passed && c.margin == Some(2.0)));
+    assert!(comparisons.iter().all(|c| c.location.file() == "tests/lib.rs"));
+    assert_eq!(comparisons[2].left, "[0.0, 0.125]");
+    assert_eq!(comparisons[5].right, "increasing");
+}
+
+#[test]
+fn deviation_of_scalars_and_collections() {
+    use nearly_eq::{Deviation, NearlyEq};
+    assert_eq!(
+        NearlyEq::deviation(&1f64, &1.5f64),
+        Some(Deviation { abs: 0.5, rel: 1.0 / 3.0, ulps: Some(1 << 51) })
+    );
+    assert_eq!(NearlyEq::deviation(&1f32, &1f32).unwrap().ulps, Some(0));
+    assert_eq!(NearlyEq::deviation(&-0f32, &0f32).unwrap().ulps, Some(0));
+    assert_eq!(
+        NearlyEq::deviation(&f64::from_bits(1), &-f64::from_bits(1)).unwrap().ulps,
+        Some(2)
+    );
+    assert_eq!(
+        NearlyEq::deviation(&2i32, &4i32),
+        Some(Deviation { abs: 2.0, rel: 0.5, ulps: None })
+    );
+    let deviation = NearlyEq::deviation(&vec![1f64, 4.0], &vec![1.5, 4.0]).unwrap();
+    assert_eq!(deviation.abs, 0.5);
+    assert_eq!(NearlyEq::deviation(&vec![1f64], &vec![1f64, 2.0]), None);
+    assert_eq!(NearlyEq::deviation(&f64::NAN, &1f64), None);
+}
+
+#[test]
+fn calibration_file_merges_records() {
+    use std::io::Write;
+    let path = report_path("merged.tsv");
+    let mut file = std::fs::File::create(&path).unwrap();
+    file.write_all(b"a.rs\t1\t5\t2\t1e-3\t1e-4\t-\na.rs\t1\t5\t3\t2e-3\t1e-5\t-\nb.rs\t2\t1\t1\t0e0\t0e0\t7\n")
+        .unwrap();
+    let sites = nearly_eq::read_calibration(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(sites.len(), 2);
+    assert_eq!(sites[0].count, 5);
+    assert_eq!(sites[0].deviation.abs, 2e-3);
+    assert_eq!(sites[0].deviation.rel, 1e-4);
+    assert_eq!(sites[1].deviation.ulps, Some(7));
+}
+
+#[test]
+fn required_tolerance_of_scalars() {
+    use nearly_eq::{RequiredTolerance, Tolerance};
+    assert_eq!(1f64.required_tolerance(&1f64), Tolerance::Any);
+    assert_eq!(1f64.required_tolerance(&1.5f64), Tolerance::Above(0.5));
+    assert_eq!(1f64.required_tolerance(&f64::NAN), Tolerance::Never);
+    assert_eq!(3u8.required_tolerance(&5u8), Tolerance::Above(2));
+    assert_eq!(100i8.required_tolerance(&-27i8), Tolerance::Above(127));
+    assert_eq!(100i8.required_tolerance(&-100i8), Tolerance::Never);
+    assert_eq!(i64::MIN.required_tolerance(&i64::MAX), Tolerance::Never);
+    assert_eq!(1f64.required_tolerance(&f64::INFINITY), Tolerance::Never);
+    assert_eq!(f64::MAX.required_tolerance(&-f64::MAX), Tolerance::Never);
+    assert_eq!(f64::INFINITY.required_tolerance(&f64::INFINITY), Tolerance::Any);
+    assert_eq!(1f64.required_ulps(&1f64), Some(0));
+    assert_eq!(1f32.required_ulps(&f32::from_bits(1f32.to_bits() + 3)), Some(3));
+    assert_eq!(1i32.required_ulps(&2i32), None);
+}
+
+#[test]
+fn required_tolerance_of_collections() {
+    use nearly_eq::{NearlyEq, RequiredTolerance, Tolerance};
+    let left = vec![1f64, 2.0, 3.0];
+    let right = vec![1.25f64, 2.0, 2.5];
+    let tolerance = left.required_tolerance(&right);
+    assert_eq!(tolerance, Tolerance::Above(0.5));
+    assert!(!tolerance.is_satisfied_by(&0.5));
+    assert!(tolerance.is_satisfied_by(&0.5000001));
+    assert!(!NearlyEq::eq(&left, &right, &0.5));
+    assert!(NearlyEq::eq(&left, &right, &0.5000001));
+    assert_eq!(left.required_tolerance(&vec![1f64]), Tolerance::Never);
+    assert_eq!([1f32, 2.0].required_tolerance(&[1f32, 2.0]), Tolerance::Any);
+    assert_eq!(Some(1f64).required_tolerance(&None), Tolerance::Never);
+    assert_eq!(Rc::new(1f64).required_tolerance(&Rc::new(3f64)), Tolerance::Above(2.0));
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn required_tolerance_of_complex() {
+    use nearly_eq::{RequiredTolerance, Tolerance};
+    let left = Complex::new(1.0f64, 0.0);
+    let right = Complex::new(1.25f64, 1.0);
+    assert_eq!(left.required_tolerance(&right), Tolerance::Above(1.0));
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn required_tolerance_of_ndarray() {
+    use nearly_eq::{RequiredTolerance, Tolerance};
+    let left = arr2(&[[1f64, 2.0], [3.0, 4.0]]);
+    let right = arr2(&[[1f64, 2.0], [3.0, 4.25]]);
+    assert_eq!(left.required_tolerance(&right), Tolerance::Above(0.25));
+    assert_eq!(left.required_tolerance(&arr2(&[[1f64, 2.0]])), Tolerance::Never);
+}
+
+#[test]
+#[cfg(feature = "num-rational")]
+fn required_tolerance_of_ratio() {
+    use nearly_eq::{RequiredTolerance, Tolerance};
+    let left = Rational64::new(1, 2);
+    let right = Rational64::new(1, 3);
+    assert_eq!(left.required_tolerance(&right), Tolerance::Above(Rational64::new(1, 6)));
+}
+
+#[test]
+#[cfg(feature = "fpa")]
+fn required_tolerance_of_fpa() {
+    use nearly_eq::{RequiredTolerance, Tolerance};
+    let left = I16F16(42.0_f32).unwrap();
+    let right = I16F16(42.5_f32).unwrap();
+    assert_eq!(left.required_tolerance(&right), Tolerance::AtLeast(I16F16(0.5_f32).unwrap()));
+    assert_eq!(left.required_ulps(&right), Some(1 << 15));
+}
+
+#[test]
+fn stats_of_vector() {
+    use nearly_eq::NearlyEq;
+    let left = vec![1f64, 2.0, 3.0, 4.0];
+    let right = vec![1f64, 2.5, 3.0, 2.0];
+    let stats = left.stats(&right, &1.0).unwrap();
+    assert_eq!(stats.count, 4);
+    assert_eq!(stats.mismatches, 1);
+    assert_eq!(stats.max_abs_error, 2.0);
+    assert_eq!(stats.max_abs_index, Some(3));
+    assert_eq!(stats.max_rel_error, 0.5);
+    assert_eq!(stats.mean_abs_error, 0.625);
+    assert_eq!(stats.rms_error, (4.25f64 / 4.0).sqrt());
+    assert_eq!(left.stats(&vec![1f64], &1.0), None);
+    assert_eq!(NearlyEq::stats(&1f64, &2f64, &1.0), None);
+}
+
+#[test]
+fn stats_of_slice_and_array() {
+    use nearly_eq::NearlyEq;
+    let left = [1f32, 2.0, 3.0];
+    let right = [1f32, 2.0, 3.5];
+    assert_eq!(left.stats(&right, &1.0).unwrap().max_abs_index, Some(2));
+    let stats = (&left as &[f32]).stats(&right as &[f32], &0.1).unwrap();
+    assert_eq!(stats.mismatches, 1);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn stats_of_ndarray() {
+    use nearly_eq::NearlyEq;
+    let left = arr2(&[[1f64, 2.0], [3.0, 4.0]]);
+    let right = arr2(&[[1f64, 2.0], [3.25, 4.0]]);
+    let stats = left.stats(&right, &0.1).unwrap();
+    assert_eq!(stats.mismatches, 1);
+    assert_eq!(stats.max_abs_index, Some(2));
+}
+
+#[test]
+#[should_panic(expected = "4 element(s), 1 mismatch(es), max abs error 2e0 at index 3")]
+fn bad_compare_with_vector_prints_stats() {
+    let left = vec![1f64, 2.0, 3.0, 4.0];
+    let right = vec![1f64, 2.5, 3.0, 2.0];
+    assert_nearly_eq!(left, right, 1f64);
+}
+
+#[test]
+fn compare_with_outliers() {
+    use nearly_eq::{NearlyEq, Outliers};
+    let left = vec![1f64, 2.0, 3.5, 4.0, 5.0];
+    let right = vec![1f64, 2.0, 3.0, 4.0, 5.0];
+    assert_nearly_eq!(Outliers::at_most(&left, 1), right, 1e-6);
+    assert_nearly_eq!(Outliers::at_most_percent(&left, 20.0), right);
+    assert_nearly_eq!(Outliers::percentile(&left, 80.0), &right as &[f64]);
+    assert!(!Outliers::at_most(&left, 0).eq(&right, &1e-6));
+    assert!(!Outliers::at_most_percent(&left, 19.0).eq(&right, &1e-6));
+    assert!(!Outliers::percentile(&left, 81.0).eq(&right, &1e-6));
+    assert_eq!(Outliers::at_most(&left, 0).outliers(&right, &1e-6), Some(vec![2]));
+    assert!(!Outliers::at_most(&left, 5).eq(&vec![1f64], &1e-6));
+}
+
+#[test]
+fn compare_with_outliers_array() {
+    use nearly_eq::Outliers;
+    let left = [1f32, 2.0, 3.5];
+    let right = [1f32, 2.0, 3.0];
+    assert_nearly_eq!(Outliers::at_most(&left, 1), right);
+}
+
+#[test]
+#[should_panic(expected = "2 outlier(s) at index [1, 2], at most 1 allowed")]
+fn bad_compare_with_outliers() {
+    use nearly_eq::Outliers;
+    let left = vec![1f64, 2.5, 3.5, 4.0];
+    let right = vec![1f64, 2.0, 3.0, 4.0];
+    assert_nearly_eq!(Outliers::at_most(&left, 1), right, 1e-6);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn compare_with_outliers_ndarray() {
+    use nearly_eq::Outliers;
+    let left = arr2(&[[1f64, 2.0], [3.0, 4.5]]);
+    let right = arr2(&[[1f64, 2.0], [3.0, 4.0]]);
+    assert_nearly_eq!(Outliers::at_most_percent(&left, 25.0), right);
+}
+
+#[test]
+fn compare_with_norm() {
+    use nearly_eq::{ByNorm, NearlyEq, Norm};
+    let left = vec![1f64, 2.0, 3.0];
+    let right = vec![1.003f64, 2.004, 3.0];
+    assert!(ByNorm::l2(&left).eq(&right, &5.0001e-3));
+    assert!(!ByNorm::l2(&left).eq(&right, &4.999e-3));
+    assert!(ByNorm::l1(&left).eq(&right, &7.0001e-3));
+    assert!(!ByNorm::l1(&left).eq(&right, &6.999e-3));
+    assert!(ByNorm::linf(&left).eq(&right, &4.0001e-3));
+    assert!(ByNorm::new(&left, Norm::LInf).rtol(1.34e-3).eq(&right, &0.0));
+    assert!(!ByNorm::l2(&left).eq(&vec![1f64, 2.0], &1.0));
+    assert_nearly_eq!(ByNorm::l2(&left), &right as &[f64], 1e-2);
+}
+
+#[test]
+fn compare_nan_with_norm() {
+    use nearly_eq::{ByNorm, NearlyEq, Norm};
+    assert!(Norm::LInf.of(vec![1.0, f64::NAN, 2.0]).is_nan());
+    assert!(Norm::LInf.of(vec![f64::NAN, 1.0]).is_nan());
+    assert!(!ByNorm::linf(&[f64::NAN]).eq(&[1f64], &1.0));
+    assert!(!ByNorm::l1(&[f64::NAN]).eq(&[1f64], &1.0));
+    assert!(!ByNorm::l2(&[f64::NAN]).eq(&[1f64], &1.0));
+}
+
+#[test]
+fn compare_points_by_euclidean_distance() {
+    use nearly_eq::ByNorm;
+    let left = [0f32, 3.0, 4.0];
+    assert_nearly_eq!(ByNorm::l2(&left), [0f32, 3.0, 4.0]);
+    assert_nearly_eq!(ByNorm::l2(&left), [0f32, 3.0006, 4.0008], 1.001e-3);
+}
+
+#[test]
+#[should_panic(expected = "L2 norm of the difference 5e0, allowed 5e-1")]
+fn bad_compare_with_norm() {
+    use nearly_eq::ByNorm;
+    let left = [0f64, 3.0, 4.0];
+    assert_nearly_eq!(ByNorm::l2(&left), [0f64, 0.0, 0.0], 0.5);
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn compare_complex_vectors_with_norm() {
+    use nearly_eq::{ByNorm, NearlyEq};
+    let left = vec![Complex::new(1.0f64, 0.0), Complex::new(0.0, 1.0)];
+    let right = vec![Complex::new(1.0f64, 3e-3), Complex::new(4e-3, 1.0)];
+    assert!(ByNorm::l2(&left).eq(&right, &5.0001e-3));
+    assert!(!ByNorm::l2(&left).eq(&right, &4.999e-3));
+}
+
+#[test]
+fn compare_with_elementwise_tolerances() {
+    use nearly_eq::{Elementwise, LengthMismatch};
+    let left = vec![1f64, 2.05, 3.0];
+    let right = vec![1f64, 2.0, 3.0];
+    assert_nearly_eq!(Elementwise::new(&left), right, vec![1e-6, 0.1, 1e-6]);
+    assert_nearly_eq!(Elementwise::new(&left[..]), [1f64, 2.0, 3.0], [1e-6, 0.1, 1e-6]);
+    assert_nearly_eq!(Elementwise::new(&left), right, &[1e-6, 0.1, 1e-6] as &[f64]);
+    assert_eq!(Elementwise::new(&left).try_eq(&right, &[1e-6, 1e-2, 1e-6]), Ok(false));
+    assert_eq!(Elementwise::new(&left).mismatches(&right, &[1e-6, 1e-2, 1e-6]), Ok(vec![1]));
+    assert_eq!(
+        Elementwise::new(&left).try_eq(&right, &[1e-6, 0.1]),
+        Err(LengthMismatch {
+            left: 3,
+            right: 3,
+            tolerances: 2,
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "lengths differ: 3 left element(s), 3 right element(s) and 2 tolerance(s)")]
+fn bad_compare_with_elementwise_tolerance_length() {
+    use nearly_eq::Elementwise;
+    let left = vec![1f64, 2.0, 3.0];
+    assert_nearly_eq!(Elementwise::new(&left), left, vec![1e-6, 1e-6]);
+}
+
+#[test]
+#[should_panic(expected = "1 mismatch(es) at index [2]")]
+fn bad_compare_with_elementwise_tolerances() {
+    use nearly_eq::Elementwise;
+    let left = [1f32, 2.0, 3.5];
+    assert_nearly_eq!(Elementwise::new(&left), [1f32, 2.0, 3.0], [1e-6, 1e-6, 0.1]);
+}
+
+#[test]
+fn compare_with_zero_padding() {
+    use nearly_eq::{NearlyEq, ZeroPadded};
+    let left = vec![1f64, 2.0, 3.0];
+    assert_nearly_eq!(ZeroPadded::trailing(&left), vec![1f64, 2.0, 3.0, 1e-9, 0.0], 1e-6);
+    assert_nearly_eq!(ZeroPadded::trailing(&left), [1f64, 2.0], 3.5);
+    assert_nearly_eq!(ZeroPadded::leading(&left), vec![0f64, 1.0, 2.0, 3.0]);
+    assert_nearly_eq!(ZeroPadded::leading(&left[..]), left);
+    assert!(!ZeroPadded::trailing(&left).eq(&vec![0f64, 1.0, 2.0, 3.0], &1e-6));
+    assert!(!ZeroPadded::leading(&left).eq(&vec![1f64, 2.0, 3.0, 0.0], &1e-6));
+}
+
+#[test]
+#[should_panic(expected = "right has 1 extra non-zero element(s) at index [3]")]
+fn bad_compare_with_trailing_zero_padding() {
+    use nearly_eq::ZeroPadded;
+    let left = vec![1f64, 2.0, 3.0];
+    assert_nearly_eq!(ZeroPadded::trailing(&left), vec![1f64, 2.0, 3.0, 0.5], 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "1 mismatch(es) at index [3], left has 2 extra non-zero element(s) at index [0, 1]")]
+fn bad_compare_with_leading_zero_padding() {
+    use nearly_eq::ZeroPadded;
+    let left = [1f32, 2.0, 3.0, 4.0];
+    assert_nearly_eq!(ZeroPadded::leading(&left), [3.0f32, 5.0]);
+}
+
+#[test]
+fn compare_unordered() {
+    use nearly_eq::{NearlyEq, Unordered};
+    let left = vec![2f64, -1.0, 0.5];
+    assert_nearly_eq!(Unordered::new(&left), vec![-1f64, 0.5, 2.0]);
+    assert_nearly_eq!(Unordered::new(&left[..]), [0.5f64, 2.0 + 1e-9, -1.0], 1e-6);
+    assert!(!Unordered::new(&left).eq(&vec![-1f64, 0.5], &1e-6));
+    assert!(!Unordered::new(&left).eq(&vec![-1f64, 0.5, 0.5], &1e-6));
+    assert_eq!(
+        Unordered::new(&left).matching(&vec![-1f64, 0.5, 2.0], &1e-6),
+        vec![Some(2), Some(0), Some(1)]
+    );
+}
+
+#[test]
+fn compare_unordered_near_ties() {
+    use nearly_eq::Unordered;
+    // A greedy pairing of 1.2 with 1.15 would leave 1.0 without a match.
+    let left = vec![1.2f64, 1.0];
+    assert_nearly_eq!(Unordered::new(&left), vec![1.15f64, 1.3], 0.2);
+}
+
+#[test]
+fn compare_unordered_long_inputs() {
+    use nearly_eq::Unordered;
+    let ordered: Vec<f64> = (0..100_000).map(f64::from).collect();
+    assert_nearly_eq!(Unordered::new(&ordered), ordered, 0.1);
+    // Only the last element is unpaired at first, and matching it shifts every other pair.
+    let left: Vec<f64> = (0..5_000).map(f64::from).collect();
+    let mut right: Vec<f64> = left.iter().map(|x| x + 0.5).collect();
+    right[4_999] = -0.5;
+    let matching = Unordered::new(&left).matching(&right, &0.6);
+    assert_eq!(matching[0], Some(4_999));
+    assert!((1..5_000).all(|i| matching[i] == Some(i - 1)));
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn compare_unordered_complex() {
+    use nearly_eq::Unordered;
+    let left = vec![Complex::new(0.0f64, 1.0), Complex::new(0.0, -1.0)];
+    let right = vec![Complex::new(0.0f64, -1.0), Complex::new(1e-9, 1.0)];
+    assert_nearly_eq!(Unordered::new(&left), right, 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "1 left element(s) unmatched at index [0], 1 right element(s) unmatched at index [1]")]
+fn bad_compare_unordered() {
+    use nearly_eq::Unordered;
+    let left = vec![2f64, -1.0, 0.5];
+    assert_nearly_eq!(Unordered::new(&left), vec![-1f64, 0.5, 0.5]);
+}
+
+#[test]
+fn compare_up_to_factor() {
+    use nearly_eq::{Factor, NearlyEq, UpTo};
+    let left = vec![0.6f64, -0.8];
+    assert_nearly_eq!(UpTo::sign(&left), vec![-0.6f64, 0.8]);
+    assert_nearly_eq!(UpTo::phase(&left[..]), [0.6f64, -0.8]);
+    assert_nearly_eq!(UpTo::scale(&left), vec![-3f64, 4.0]);
+    assert!(!UpTo::sign(&left).eq(&vec![3f64, -4.0], &1e-6));
+    assert!(!UpTo::new(&left, Factor::Scale).eq(&vec![3f64, 4.0], &1e-6));
+    assert_eq!(UpTo::scale(&left).fitted(&vec![3f64, -4.0]), Some(0.2));
+    assert_eq!(UpTo::sign(&left).fitted(&vec![3f64]), None);
+}
+
+#[test]
+fn compare_up_to_degenerate_scale() {
+    use nearly_eq::{NearlyEq, UpTo};
+    let zeros = vec![0f64, 0.0];
+    assert_eq!(UpTo::scale(&zeros).fitted(&vec![3f64, -4.0]), Some(1.0));
+    assert!(!UpTo::scale(&zeros).eq(&vec![3f64, -4.0], &1e-6));
+    assert_eq!(UpTo::scale(&vec![3f64, -4.0]).fitted(&zeros), Some(1.0));
+    assert!(!UpTo::scale(&vec![3f64, -4.0]).eq(&zeros, &1e-6));
+    assert_eq!(UpTo::scale(&vec![1f64]).fitted(&vec![1e-200f64]), Some(1.0));
+    assert_nearly_eq!(UpTo::scale(&zeros), zeros);
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn compare_complex_up_to_phase() {
+    use nearly_eq::{NearlyEq, UpTo};
+    let left = vec![Complex::new(1.0f64, 0.0), Complex::new(0.0, 1.0)];
+    let phase = Complex::new(0.6f64, 0.8);
+    let right: Vec<Complex<f64>> = left.iter().map(|x| x * phase).collect();
+    assert_nearly_eq!(UpTo::phase(&left), right);
+    assert!(!UpTo::sign(&left).eq(&right, &1e-6));
+    let right: Vec<Complex<f64>> = left.iter().map(|x| x * 3.0 * phase).collect();
+    assert!(!UpTo::phase(&left).eq(&right, &1e-6));
+    assert_nearly_eq!(UpTo::scale(&left), right);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn compare_ndarray_columns_up_to_sign() {
+    use nearly_eq::UpTo;
+    let left = arr2(&[[0.6f64, 0.8], [-0.8, 0.6]]);
+    let right = arr2(&[[-0.6f64, 0.8], [0.8, 0.6]]);
+    for i in 0..2 {
+        let column = left.column(i);
+        assert_nearly_eq!(UpTo::sign(&column), right.column(i));
+    }
+}
+
+#[test]
+#[should_panic(expected = "best-fitting Sign factor 1.0")]
+fn bad_compare_up_to_sign() {
+    use nearly_eq::UpTo;
+    let left = vec![0.6f64, -0.8];
+    assert_nearly_eq!(UpTo::sign(&left), vec![3f64, -4.0]);
+}
+
+#[test]
+fn compare_with_full_scale() {
+    use nearly_eq::{FullScale, NearlyEq};
+    let left = vec![0.0005f64, 5.0, 10.004];
+    let right = vec![0f64, 5.0, 10.0];
+    assert_nearly_eq!(FullScale::new(&left), right, 1e-3);
+    assert_nearly_eq!(FullScale::new(&left[..]).full_scale(20.0), [0f64, 5.0, 10.0], 1e-3);
+    assert!(!FullScale::new(&left).eq(&right, &3e-4));
+    assert!(!FullScale::new(&left).full_scale(1.0).eq(&right, &1e-3));
+    assert!(!FullScale::new(&left).eq(&vec![0f64, 5.0], &1.0));
+    assert_eq!(FullScale::new(&left).range(&right), 10.0);
+    assert_eq!(FullScale::new(&left).max_error(&right).map(|(_, i)| i), Some(2));
+}
+
+#[test]
+fn compare_nan_with_full_scale() {
+    use nearly_eq::{FullScale, NearlyEq};
+    let left = vec![0f64, 5.0, 10.0];
+    let right = vec![0f64, f64::NAN, 10.0];
+    assert!(FullScale::new(&left).range(&right).is_nan());
+    assert!(FullScale::new(&right).range(&[f64::NAN, 1.0]).is_nan());
+    assert!(!FullScale::new(&left).eq(&right, &1.0));
+    assert_eq!(FullScale::new(&left).margin(&right, &1.0), None);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn compare_ndarray_with_full_scale() {
+    use nearly_eq::FullScale;
+    let left = arr2(&[[0.001f32, 1.0], [-2.0, 4.0]]);
+    let right = arr2(&[[0f32, 1.0], [-2.0, 4.0]]);
+    assert_nearly_eq!(FullScale::new(&left), right, 1e-3);
+}
+
+#[test]
+#[should_panic(expected = "max error 2e-2 at index 0, allowed 1e-2 (0.001 of full scale 1e1)")]
+fn bad_compare_with_full_scale() {
+    use nearly_eq::FullScale;
+    let left = vec![0.02f64, 5.0, 10.0];
+    assert_nearly_eq!(FullScale::new(&left), vec![0f64, 5.0, 10.0], 1e-3);
+}
+
+#[test]
+fn assert_sorted() {
+    assert_nearly_sorted!([0.1f64, 0.5, 0.5 - 1e-13, 1.0]);
+    assert_nearly_sorted!(vec![1f32, 0.99, 2.0], 0.02);
+    assert_nearly_sorted!(Vec::<f64>::new());
+    debug_assert_nearly_sorted!(&[1i32, 1, 2] as &[i32]);
+}
+
+#[test]
+fn assert_monotonic() {
+    use nearly_eq::{monotonic_violation, Monotonic};
+    let values = vec![1f64, 1.05, 2.0];
+    assert_nearly_monotonic!(values, Monotonic::Increasing);
+    assert_nearly_monotonic!(values, Monotonic::StrictlyIncreasing, 0.01);
+    assert_nearly_monotonic!([3f64, 2.0, 2.0 + 1e-13], Monotonic::Decreasing);
+    assert_nearly_monotonic!([3f64, 2.0, 1.0], Monotonic::StrictlyDecreasing, 0.5);
+    debug_assert_nearly_monotonic!([3f64, 2.0, 1.0], Monotonic::StrictlyDecreasing);
+    assert_eq!(monotonic_violation(&values, &0.1, Monotonic::StrictlyIncreasing), Some((0, 1)));
+    assert_eq!(monotonic_violation(&values, &0.1, Monotonic::Decreasing), Some((0, 2)));
+    assert_eq!(monotonic_violation(&values, &0.1, Monotonic::Increasing), None);
+}
+
+#[test]
+fn monotonic_cumulative_drift() {
+    use nearly_eq::{monotonic_violation, Monotonic};
+    let values: Vec<f64> = (0..10).map(|i| 1.0 - f64::from(i) * 0.01).collect();
+    assert_eq!(monotonic_violation(&values, &0.015, Monotonic::Increasing), Some((0, 2)));
+    let values: Vec<f64> = (0..10).map(|i| f64::from(i) * 0.01).collect();
+    assert_eq!(monotonic_violation(&values, &0.015, Monotonic::Decreasing), Some((0, 2)));
+    assert_eq!(monotonic_violation(&[1f64, 0.99, 2.0, 1.995], &0.015, Monotonic::Increasing), None);
+}
+
+#[test]
+#[should_panic(expected = "elements at index 0 and 2 (`1.0` and `0.98`) are not nearly increasing")]
+fn bad_assert_monotonic_drift() {
+    use nearly_eq::Monotonic;
+    assert_nearly_monotonic!([1f64, 0.99, 0.98], Monotonic::Increasing, 0.015);
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn assert_ndarray_monotonic() {
+    use nearly_eq::Monotonic;
+    let values = arr2(&[[1f64, 2.0], [1.5, 0.5]]);
+    let column = values.column(0);
+    assert_nearly_sorted!(column);
+    let row = values.row(1);
+    assert_nearly_monotonic!(row, Monotonic::StrictlyDecreasing);
+}
+
+#[test]
+#[should_panic(expected = "elements at index 1 and 2 (`0.5` and `0.4`) are not nearly increasing")]
+fn bad_assert_sorted() {
+    assert_nearly_sorted!([0.1f64, 0.5, 0.4, 1.0]);
+}
+
+#[test]
+#[should_panic(expected = "elements at index 0 and 1 (`1.0` and `1.05`) are not nearly strictly increasing")]
+fn bad_assert_monotonic() {
+    use nearly_eq::Monotonic;
+    assert_nearly_monotonic!(vec![1f64, 1.05, 2.0], Monotonic::StrictlyIncreasing, 0.1);
+}
+
+#[test]
+fn compare_nearly_ord() {
+    use nearly_eq::{nearly_sort_cmp, NearlyOrd};
+    use std::cmp::Ordering;
+    assert!(1f64.nearly_le(&(1.0 - 1e-13), &1e-12));
+    assert!(!1f64.nearly_lt(&(1.0 + 1e-13), &1e-12));
+    assert!(1f64.nearly_lt(&1.1, &1e-12));
+    assert!(1f32.nearly_ge(&(1.0 + 1e-7), &1e-6));
+    assert!(!1f32.nearly_gt(&(1.0 - 1e-7), &1e-6));
+    assert_eq!(5i32.nearly_cmp(&7, &3), Some(Ordering::Equal));
+    assert_eq!(5u8.nearly_cmp(&7, &1), Some(Ordering::Less));
+    assert_eq!(f64::NAN.nearly_cmp(&1.0, &1e-12), None);
+    let mut values = vec![2f64, 1.25, -0.0, 1.0, 0.0, -0.25];
+    values.sort_by(|a, b| nearly_sort_cmp(a, b, 0.5));
+    assert_eq!(values, vec![-0.25, -0.0, 0.0, 1.25, 1.0, 2.0]);
+    assert_eq!(nearly_sort_cmp(&0.375f64, &0.5, 0.5), Ordering::Less);
+    assert_eq!(nearly_sort_cmp(&[1f64, 2.0], &[1.25, 1.0], 0.5), Ordering::Greater);
+}
+
+#[test]
+fn assert_nearly_ordered() {
+    assert_nearly_le!(1f64, 2f64);
+    assert_nearly_le!(1f64 + 1e-13, 1f64);
+    assert_nearly_le!(10i64, 9i64, 2i64);
+    assert_nearly_ge!(2f32, 1f32);
+    assert_nearly_ge!(1f64, 1.05f64, 0.1f64);
+    debug_assert_nearly_le!(1u32, 1u32);
+    debug_assert_nearly_ge!(1u32, 1u32, 0u32);
+}
+
+#[test]
+#[cfg(feature = "num-rational")]
+fn assert_nearly_ordered_rational() {
+    assert_nearly_le!(Rational64::new(3, 2), Rational64::new(1, 1), Rational64::new(1, 1));
+    assert_nearly_ge!(Rational64::new(1, 2), Rational64::new(1, 3));
+}
+
+#[test]
+#[cfg(feature = "fpa")]
+fn assert_nearly_ordered_fpa() {
+    let left = I16F16(42.00001_f32).unwrap();
+    let right = I16F16(42.00000_f32).unwrap();
+    assert_nearly_le!(left, right);
+    assert_nearly_ge!(right, left);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `(left <= right)` (left: `1.1` , right: `1.0`, eps: `0.05`)")]
+fn bad_assert_nearly_le() {
+    assert_nearly_le!(1.1f64, 1f64, 0.05f64);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `(left >= right)` (left: `1.0` , right: `1.1`)")]
+fn bad_assert_nearly_ge() {
+    assert_nearly_ge!(1f64, 1.1f64);
+}
+
+#[test]
+fn compare_nearly_zero() {
+    use nearly_eq::NearlyZero;
+    assert!(1e-13f64.is_nearly_zero(&1e-12));
+    assert!(!1e-11f64.is_nearly_zero(&1e-12));
+    assert!((-3.0000001f32).is_nearly_integer(&1e-6));
+    assert!(!2.5f64.is_nearly_integer(&0.1));
+    assert!(!f64::INFINITY.is_nearly_integer(&0.1));
+    assert!(0.9999999f32.is_nearly_one(&1e-6));
+    assert!(2i32.is_nearly_zero(&3));
+    assert!(7u8.is_nearly_integer(&0));
+    assert!(!3i64.is_nearly_one(&1));
+    assert!(vec![1e-13f64, -1e-13].is_nearly_zero(&1e-12));
+    assert!([1.0f64, 2.0 + 1e-13].is_nearly_integer(&1e-12));
+    assert!(![1.0f64, 1.5][..].is_nearly_one(&1e-12));
+}
+
+#[test]
+fn compare_signed_extremes() {
+    use nearly_eq::{NearlyEq, NearlyZero};
+    assert!(!i8::MIN.is_nearly_zero(&1));
+    assert!(!i8::MIN.is_nearly_one(&i8::MAX));
+    assert!((-5i8).is_nearly_zero(&6));
+    assert!(!NearlyEq::eq(&i8::MIN, &i8::MAX, &i8::MAX));
+    assert!(!NearlyEq::eq(&i64::MIN, &1i64, &i64::MAX));
+    assert!(NearlyEq::eq(&i32::MIN, &(i32::MIN + 1), &2));
+    assert!(!NearlyEq::eq(&1i32, &2i32, &-5));
+}
+
+#[test]
+fn assert_nearly_zero_integer_one() {
+    assert_nearly_zero!(1e-13f64);
+    assert_nearly_zero!(0.5f64, 0.6f64);
+    assert_nearly_integer!(2.9999999f32);
+    assert_nearly_integer!(vec![1f64, 2.0 - 1e-13]);
+    assert_nearly_one!([1f64, 1.0 + 1e-13]);
+    assert_nearly_one!(2i32, 2i32);
+    debug_assert_nearly_zero!(0f32);
+    debug_assert_nearly_integer!(1f32, 0.1f32);
+    debug_assert_nearly_one!(1f32);
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn assert_nearly_zero_complex() {
+    assert_nearly_zero!(Complex::new(1e-13f64, -1e-13));
+    assert_nearly_integer!(Complex::new(1.0f64 + 1e-13, -2.0));
+    assert_nearly_one!(Complex::new(1.0f64, 1e-13));
+}
+
+#[test]
+#[cfg(feature = "num-rational")]
+fn assert_nearly_zero_rational() {
+    assert_nearly_zero!(Rational64::new(0, 1));
+    assert_nearly_integer!(Rational64::new(301, 100), Rational64::new(1, 50));
+    assert_nearly_one!(Rational64::new(99, 100), Rational64::new(1, 50));
+}
+
+#[test]
+#[cfg(feature = "fpa")]
+fn assert_nearly_zero_fpa() {
+    let eps = I16F16(0.001_f32).unwrap();
+    assert_nearly_zero!(I16F16(0.0001_f32).unwrap(), eps);
+    assert_nearly_integer!(I16F16(-2.9999_f32).unwrap(), eps);
+    assert_nearly_one!(I16F16(1.0001_f32).unwrap(), eps);
+    assert!(!nearly_eq::NearlyZero::is_nearly_one(&I16F16(1.1_f32).unwrap(), &eps));
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn assert_nearly_zero_ndarray() {
+    assert_nearly_zero!(arr2(&[[1e-13f64, 0.0], [0.0, -1e-13]]));
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `value is nearly an integer` (value: `2.5`)")]
+fn bad_assert_nearly_integer() {
+    assert_nearly_integer!(2.5f64);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `value is nearly zero` (value: `0.001`, eps: `0.0001`)")]
+fn bad_assert_nearly_zero() {
+    assert_nearly_zero!(1e-3f64, 1e-4f64);
+}
+
+#[test]
+fn search_nearly_in_slices() {
+    use nearly_eq::NearlySliceExt;
+    let grid: Vec<f64> = (0..10).map(|i| i as f64 * 0.1).collect();
+    assert!(grid.nearly_contains(&0.3, &1e-12));
+    assert!(!grid.nearly_contains(&0.35, &1e-12));
+    assert_eq!(grid.nearly_position(&0.3, &1e-12), Some(3));
+    assert_eq!(grid.nearly_position(&0.35, &0.06), Some(3));
+    assert_eq!(grid.nearly_rposition(&0.35, &0.06), Some(4));
+    assert_eq!(grid[..].nearly_rposition(&2.0, &0.06), None);
+    assert_eq!(grid.nearly_binary_search(&0.7, &1e-12), Ok(7));
+    assert_eq!(grid.nearly_binary_search(&0.35, &1e-12), Err(4));
+    assert_eq!(grid.nearly_binary_search(&-1.0, &1e-12), Err(0));
+    assert_eq!([1i32, 5, 9].nearly_binary_search(&6, &2), Ok(1));
+    let pairs = [vec![1f64, 2.0], vec![3f64, 4.0]];
+    assert_eq!(pairs.nearly_position(&vec![3f64, 4.0 + 1e-13], &1e-12), Some(1));
+}
+
+#[test]
+fn dedup_nearly_equal_values() {
+    use nearly_eq::NearlyVecExt;
+    let mut values = vec![1f64, 1.0 + 1e-13, 2.0, 2.0 + 1e-13, 2.0 + 2e-13, 3.0];
+    values.nearly_dedup(&1.5e-13);
+    assert_eq!(values, vec![1f64, 2.0, 3.0]);
+    let mut values: Vec<f32> = Vec::new();
+    values.nearly_dedup(&1e-6);
+    assert!(values.is_empty());
+    let mut points = vec![[0f64, 0.0], [1e-13, 0.0], [1.0, 1.0]];
+    points.nearly_dedup(&1e-12);
+    assert_eq!(points, vec![[0f64, 0.0], [1.0, 1.0]]);
+}
+
+#[test]
+fn cluster_nearly_equal_values() {
+    use nearly_eq::NearlySliceExt;
+    // 0.0 and 0.2 are linked through 0.1, whatever the order.
+    let values = [0.2f64, 5.0, 0.0, 5.05, 0.1];
+    assert_eq!(values.nearly_cluster(&0.11), vec![vec![0, 2, 4], vec![1, 3]]);
+    let values = [0.0f64, 0.2, 0.1];
+    assert_eq!(values.nearly_cluster(&0.11), vec![vec![0, 1, 2]]);
+    let points = [[0f64, 0.0], [1.0, 1.0], [1e-13, 0.0], [1.0, 1.0 + 1e-13]];
+    assert_eq!(points.nearly_cluster(&1e-12), vec![vec![0, 2], vec![1, 3]]);
+    assert!(Vec::<f64>::new().nearly_cluster(&1e-12).is_empty());
+}
+
+#[test]
+fn quantized_keys() {
+    use nearly_eq::{NearlyKey, Quantized};
+    use std::collections::{BTreeSet, HashMap};
+    let mut cache = HashMap::new();
+    cache.insert(Quantized::new([0.1f64, 0.2], 1e-6), 1);
+    cache.insert(Quantized::new([0.5f64, 0.5], 1e-6), 2);
+    assert_eq!(cache.get(&Quantized::new([0.1f64 + 1e-9, 0.2], 1e-6)), Some(&1));
+    assert_eq!(Quantized::new([0.1f64 - 2e-7, 0.2], 1e-6).find_in(&cache), Some(&1));
+    assert_eq!(Quantized::new([0.5f64, 0.5 + 9e-7], 1e-6).find_in(&cache), Some(&2));
+    assert_eq!(Quantized::new([0.5f64, 0.5 + 2e-6], 1e-6).find_in(&cache), None);
+    assert_eq!(Quantized::new(0.5f32, 0.25).neighbors().len(), 3);
+    assert_eq!(Quantized::new([0.5f32, 1.0], 0.25).neighbors().len(), 9);
+    assert_eq!(*Quantized::new(0.74f64, 0.25).key(), Some(2));
+    assert_eq!((0.3f64, -0.3f32).key(0.25), (Some(1), Some(-2)));
+    assert_eq!((0.3f64, 1f64, 2f32).neighbor_keys(1.0).len(), 27);
+    let set: BTreeSet<_> = [0.6f64, 0.1, 0.65, 0.3].iter().map(|&x| Quantized::new(x, 0.25)).collect();
+    let keys: Vec<Option<i64>> = set.iter().map(|q| *q.key()).collect();
+    assert_eq!(keys, vec![Some(0), Some(1), Some(2)]);
+    assert_eq!(set.into_iter().next().map(Quantized::into_inner), Some(0.1));
+}
+
+#[test]
+fn quantized_nan_and_cell_sizes() {
+    use nearly_eq::{NearlyKey, Quantized};
+    use std::collections::HashSet;
+    assert_eq!(f64::NAN.key(1.0), None);
+    assert_eq!(f32::NAN.neighbor_keys(1.0), vec![None]);
+    assert_ne!(Quantized::new(f64::NAN, 1.0), Quantized::new(0.5f64, 1.0));
+    assert_ne!(Quantized::new(0.5f64, 1.0), Quantized::new(0.5f64, 2.0));
+    assert!(Quantized::new(0.5f64, 1.0) < Quantized::new(0.5f64, 2.0));
+    let set: HashSet<_> = [(0.5f64, 1.0), (0.5, 2.0), (0.7, 1.0)]
+        .iter()
+        .map(|&(x, cell)| Quantized::new(x, cell))
+        .collect();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn quantized_complex_keys() {
+    use nearly_eq::Quantized;
+    use std::collections::HashMap;
+    let mut cache = HashMap::new();
+    cache.insert(Quantized::new(Complex::new(1.0f64, -1.0), 1e-6), "root");
+    assert_eq!(Quantized::new(Complex::new(1.0f64 - 1e-7, -1.0 + 1e-7), 1e-6).find_in(&cache), Some(&"root"));
+    assert_eq!(Quantized::new(Complex::new(1.0f64, 1.0), 1e-6).neighbors().len(), 9);
+}
+
+#[test]
+fn compare_with_nearly_wrapper() {
+    use nearly_eq::{Nearly, NearlyWith};
+
+    #[derive(Debug, PartialEq)]
+    struct Sample {
+        name: &'static str,
+        value: Nearly<f64>,
+        weights: Nearly<Vec<f32>>,
+    }
+
+    assert_eq!(
+        Sample { name: "a", value: Nearly(0.1 + 0.2), weights: Nearly(vec![0.1 + 0.2]) },
+        Sample { name: "a", value: Nearly(0.3), weights: Nearly(vec![0.3]) }
+    );
+    let values: Vec<Nearly<f64>> = [1f64, 0.3].iter().cloned().map(Nearly).collect();
+    assert!(values.contains(&Nearly(0.1 + 0.2)));
+    assert_ne!(Nearly(1f64), Nearly(1.1));
+    assert_eq!(Nearly(Some([1f64, 2.0])), Nearly(Some([1f64, 2.0 + 1e-13])));
+    let (left, right) = (vec![0.1 + 0.2, 1.0], vec![0.3f64, 1.0]);
+    assert_eq!(Nearly::from_ref(&left[..]), Nearly::from_ref(&right[..]));
+    assert_eq!(Nearly::from_ref(&left).len(), 2);
+    assert_eq!(format!("{:?}", Nearly(vec![1f64])), "[1.0]");
+
+    assert_eq!(NearlyWith::new(1f64, 0.2), NearlyWith::new(1.1f64, 0.2));
+    assert_ne!(NearlyWith::new(1f64, 0.01), NearlyWith::new(1.1f64, 0.01));
+    assert_eq!(NearlyWith::new(vec![1i32, 5], 2), NearlyWith::new(vec![2i32, 4], 2));
+    assert_eq!(format!("{:?}", NearlyWith::new(1f64, 0.2)), "1.0");
+    assert_eq!(NearlyWith::new(1f64, 0.2).into_inner(), 1.0);
+}
+
+#[test]
+fn nearly_with_is_symmetric() {
+    use nearly_eq::NearlyWith;
+    let values = [
+        NearlyWith::new(1f64, 0.2),
+        NearlyWith::new(1.1, 0.01),
+        NearlyWith::new(1.15, 0.3),
+        NearlyWith::new(1.0, f64::NAN),
+    ];
+    for a in &values {
+        for b in &values {
+            assert_eq!(a == b, b == a, "{:?} and {:?}", a.eps, b.eps);
+        }
+    }
+    assert_ne!(NearlyWith::new(1f64, 0.2), NearlyWith::new(1.1f64, 0.01));
+    assert_ne!(NearlyWith::new(1.1f64, 0.01), NearlyWith::new(1f64, 0.2));
+    assert_eq!(NearlyWith::new(1f64, 0.2), NearlyWith::new(1.15f64, 0.3));
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn compare_complex_with_nearly_wrapper() {
+    use nearly_eq::Nearly;
+    assert_eq!(Nearly(Complex::new(0.1f64 + 0.2, 1.0)), Nearly(Complex::new(0.3f64, 1.0)));
+}
+
+#[test]
+#[cfg(feature = "ndarray")]
+fn compare_ndarray_with_nearly_wrapper() {
+    use nearly_eq::Nearly;
+    let left = arr2(&[[0.1f64 + 0.2, 1.0]]);
+    assert_eq!(Nearly::from_ref(&left), Nearly::from_ref(&arr2(&[[0.3f64, 1.0]])));
+}
+
+#[test]
+#[should_panic(expected = "left: 1.0\n right: 1.1")]
+fn bad_compare_with_nearly_wrapper() {
+    use nearly_eq::Nearly;
+    assert_eq!(Nearly(1f64), Nearly(1.1f64));
+}
+
+#[test]
+fn compare_with_type_level_tolerance() {
+    use nearly_eq::{AbsTol, Nearly, NearlyEq, Tolerant, Ulps};
+
+    type Coarse = AbsTol<f64, 5, -3>;
+
+    #[derive(Debug, PartialEq)]
+    struct Reading {
+        level: Tolerant<f64, Coarse>,
+        gain: Tolerant<f32, Ulps<2>>,
+        taps: Tolerant<Vec<f64>, AbsTol<f64, 1, -9>>,
+    }
+
+    assert_eq!(Coarse::value(), 5e-3);
+    assert_eq!(AbsTol::<f32, 25, -1>::value(), 2.5);
+    assert_eq!(format!("{:?}", Coarse::default()), "AbsTol(5e-3)");
+
+    assert_eq!(
+        Reading { level: Tolerant::new(1.0), gain: Tolerant::new(1.0), taps: Tolerant::new(vec![0.3, 1.0]) },
+        Reading {
+            level: Tolerant::new(1.004),
+            gain: Tolerant::new(1.0 + f32::EPSILON),
+            taps: Tolerant::new(vec![0.1 + 0.2, 1.0]),
+        }
+    );
+    assert_ne!(Tolerant::<f64, Coarse>::new(1.0), Tolerant::new(1.006));
+    assert_ne!(Tolerant::<f32, Ulps<2>>::new(1.0), Tolerant::new(1.0 + 3.0 * f32::EPSILON));
+    assert_ne!(Tolerant::<f64, Ulps<2>>::new(f64::NAN), Tolerant::new(f64::NAN));
+    assert_ne!(Tolerant::<Vec<f64>, Coarse>::new(vec![1.0]), Tolerant::new(vec![1.0, 2.0]));
+
+    assert_nearly_eq!(Tolerant::<f64, Coarse>::new(1.0), Tolerant::new(1.004));
+    assert_nearly_eq!(Tolerant::<[f64; 2], Ulps<1>>::new([1.0, -0.0]), Tolerant::new([1.0, 0.0]));
+    assert_eq!(Nearly(Tolerant::<f64, Coarse>::new(1.0)), Nearly(Tolerant::new(1.004)));
+
+    let margin = NearlyEq::margin(&Tolerant::<f64, Ulps<4>>::new(1.0), &Tolerant::new(1.0 + f64::EPSILON), &Ulps);
+    assert_eq!(margin, Some(0.25));
+    assert_eq!(format!("{:?}", Tolerant::<f64, Coarse>::new(1.5)), "1.5");
+    assert_eq!(*Tolerant::<f64, Coarse>::new(1.5), 1.5);
+    assert_eq!(Tolerant::<f64, Coarse>::new(1.5).into_inner(), 1.5);
+}
+
+#[test]
+fn compare_primitives_with_type_level_tolerance() {
+    use nearly_eq::{AbsTol, TolerancePolicy, Ulps};
+    assert_eq!(AbsTol::<f64, 1, -9>::value(), 1e-9);
+    assert_eq!(AbsTol::<f64, 3, -1>::value(), 0.3);
+    assert_eq!(AbsTol::<f32, 1, -9>::value(), 1e-9f32);
+    assert_eq!(AbsTol::<f64, 25, 2>::value(), 2500.0);
+
+    assert_eq!(AbsTol::<f32, 1, -10>::value(), 1e-10f32);
+
+    assert!(AbsTol::<f64, 1, -3>::default().accepts(&1.0, &1.0005));
+    assert!(AbsTol::<f32, 1, -3>::default().accepts(&1.0, &1.0005));
+    assert!(Ulps::<1>.accepts(&1.0f64, &(1.0 + f64::EPSILON)));
+    assert!(Ulps::<1>.accepts(&vec![1.0f32, 2.0], &vec![1.0, 2.0 + 2.0 * f32::EPSILON]));
+    assert!(!AbsTol::<f64, 1, -9>::default().accepts(&1.0, &1.0005));
+    assert!(!Ulps::<1>.accepts(&1.0f32, &(1.0 + 2.0 * f32::EPSILON)));
+    assert_eq!(Ulps::<4>.margin_of(&1.0f64, &(1.0 + f64::EPSILON)), Some(0.25));
+    assert_nearly_eq!(1f64, 1.5f64, 0.6f64);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `(left == right)`")]
+fn bad_compare_with_type_level_tolerance() {
+    use nearly_eq::{AbsTol, Tolerant};
+    assert_nearly_eq!(Tolerant::<f64, AbsTol<f64, 1, -9>>::new(1.0), Tolerant::new(1.0005));
+}
+
+#[test]
+fn compare_without_default_tolerance() {
+    use nearly_eq::NearlyEq;
+
+    #[derive(Debug)]
+    struct Celsius(f64);
+
+    impl NearlyEq<Celsius, f64> for Celsius {
+        fn eq(&self, other: &Celsius, eps: &f64) -> bool {
+            NearlyEq::eq(&self.0, &other.0, eps)
+        }
+    }
+
+    assert_nearly_eq!(Celsius(20.0), Celsius(20.05), 0.1);
+    assert!(NearlyEq::ne(&Celsius(20.0), &Celsius(20.5), &0.1));
+
+    let values: [&dyn NearlyEq<Celsius, f64>; 2] = [&Celsius(20.0), &Celsius(30.0)];
+    assert!(values[0].eq(&Celsius(20.05), &0.1));
+    assert!(values[1].ne(&Celsius(20.05), &0.1));
+}
+
+#[test]
+#[allow(deprecated)]
+fn compare_with_default_tolerance() {
+    use nearly_eq::{DefaultTolerance, NearlyEq};
+
+    assert_eq!(f32::default_tolerance(), 1e-6);
+    assert_eq!(<Vec<f64> as DefaultTolerance<f64>>::default_tolerance(), 1e-11);
+    assert_eq!(<[f64] as DefaultTolerance<f64>>::default_tolerance(), 1e-11);
+    assert_eq!(<f64 as NearlyEq>::eps(), 1e-11);
+    assert_eq!(<Vec<i32> as NearlyEq<Vec<i32>, i32>>::eps(), 0);
+}
+
+#[test]
+fn compare_dyn_values() {
+    use nearly_eq::{DynNearlyEq, TypeMismatch};
+
+    let fixtures: Vec<Box<dyn DynNearlyEq>> = vec![Box::new(0.3f64), Box::new(vec![1f64, 2.0]), Box::new(Some(5f64))];
+    let results: Vec<Box<dyn DynNearlyEq>> = vec![Box::new(0.1f64 + 0.2), Box::new(vec![1f64, 2.0 + 1e-12]), Box::new(Some(5.5f64))];
+
+    assert!(fixtures[0].dyn_eq(results[0].as_any(), 1e-9));
+    assert!(fixtures[1].dyn_eq(results[1].as_any(), 1e-9));
+    assert!(!fixtures[2].dyn_eq(results[2].as_any(), 1e-9));
+    assert!(fixtures[2].dyn_eq(results[2].as_any(), 1.0));
+    assert!(!fixtures[0].dyn_eq(results[1].as_any(), 1e-9));
+    assert_eq!(fixtures[2].dyn_margin(results[2].as_any(), 1.0), Some(0.5));
+    assert_eq!(fixtures[0].dyn_margin(results[1].as_any(), 1.0), None);
+
+    assert_eq!(0.3f64.try_dyn_eq(&(0.1f64 + 0.2), 1e-9), Ok(true));
+    assert_eq!(0.3f64.try_dyn_eq(&1f64, 1e-9), Ok(false));
+    assert_eq!(0.3f64.try_dyn_eq(&1i32, 1e-9), Err(TypeMismatch { expected: "f64" }));
+    assert_eq!(TypeMismatch { expected: "f64" }.to_string(), "types differ: the right value is not a `f64`");
+
+    assert_nearly_eq!(fixtures[1], results[1], 1e-9);
+    assert_nearly_eq!(fixtures[0], results[0]);
+}
+
+#[test]
+fn compare_dyn_values_through_boxes() {
+    use nearly_eq::{DynNearlyEq, TypeMismatch};
+
+    let fixtures: Vec<Box<dyn DynNearlyEq>> = vec![Box::new(0.3f64), Box::new(vec![1f64, 2.0])];
+
+    assert_eq!(fixtures[0].try_dyn_eq(&0.3f64, 1e-9), Ok(true));
+    assert_eq!(fixtures[0].try_dyn_eq(&1f64, 1e-9), Ok(false));
+    assert_eq!(fixtures[0].try_dyn_eq(&1i32, 1e-9), Err(TypeMismatch { expected: "f64" }));
+    assert_eq!(fixtures[0].dyn_margin(&0.3f64, 1e-9), Some(0.0));
+    assert_eq!(fixtures[0].as_any().downcast_ref::<f64>(), Some(&0.3));
+    assert_eq!(fixtures[1].as_any().downcast_ref::<Vec<f64>>(), Some(&vec![1f64, 2.0]));
+
+    assert_eq!(0.3f64.try_dyn_eq(&fixtures[0], 1e-9), Ok(true));
+    assert_eq!(fixtures[0].try_dyn_eq(&fixtures[0], 1e-9), Ok(true));
+    assert_eq!(fixtures[0].try_dyn_eq(&fixtures[1], 1e-9), Err(TypeMismatch { expected: "f64" }));
+    assert_eq!(
+        fixtures[0].dyn_explain(&1i32, 1e-9),
+        Some("types differ: the right value is not a `f64`".to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "types differ: the right value is not a `f64`")]
+fn bad_compare_dyn_values() {
+    use nearly_eq::DynNearlyEq;
+    let left: Box<dyn DynNearlyEq> = Box::new(1f64);
+    let right: Box<dyn DynNearlyEq> = Box::new(vec![1f64]);
+    assert_nearly_eq!(left, right, 1e-9);
+}
+
+#[test]
+#[cfg(feature = "float")]
+fn compare_generic_floats() {
+    use nearly_eq::{FloatCmp, MaxUlps, NearlyEq, Relative};
+    use num_traits::Float;
+
+    fn mean<T: Float>(values: &[T]) -> T {
+        values.iter().fold(T::zero(), |sum, &x| sum + x) / T::from(values.len()).unwrap()
+    }
+
+    fn check<T: Float + std::fmt::Debug>(values: &[T], expected: T) {
+        assert_nearly_eq!(FloatCmp(mean(values)), FloatCmp(expected));
+        assert_nearly_eq!(FloatCmp(mean(values)), FloatCmp(expected), Relative(T::epsilon() * T::from(4).unwrap()));
+    }
+
+    check(&[0.1f32, 0.2, 0.3], 0.2);
+    check(&[0.1f64, 0.2, 0.3], 0.2);
+
+    assert!(NearlyEq::eq(&FloatCmp(1e10f64), &FloatCmp(1e10 + 1.0), &Relative(1e-9)));
+    assert!(NearlyEq::ne(&FloatCmp(1e10f64), &FloatCmp(1e10 + 100.0), &Relative(1e-9)));
+    assert!(NearlyEq::eq(&FloatCmp(1f32), &FloatCmp(1.0 + f32::EPSILON), &MaxUlps(1)));
+    assert!(NearlyEq::ne(&FloatCmp(1f32), &FloatCmp(1.0 + 2.0 * f32::EPSILON), &MaxUlps(1)));
+    assert!(NearlyEq::ne(&FloatCmp(f64::NAN), &FloatCmp(f64::NAN), &MaxUlps(u64::MAX)));
+    assert!(NearlyEq::eq(&FloatCmp(f64::INFINITY), &FloatCmp(f64::INFINITY), &MaxUlps(0)));
+
+    // Steps across zero, subnormals and binade boundaries count one ULP each.
+    let tiny = f64::from_bits(1);
+    assert!(NearlyEq::eq(&FloatCmp(-tiny), &FloatCmp(tiny), &MaxUlps(2)));
+    assert!(NearlyEq::ne(&FloatCmp(-tiny), &FloatCmp(tiny), &MaxUlps(1)));
+    let normal = f64::MIN_POSITIVE;
+    let below = f64::from_bits(normal.to_bits() - 1);
+    assert!(NearlyEq::eq(&FloatCmp(below), &FloatCmp(normal), &MaxUlps(1)));
+    let below_two = f32::from_bits(2f32.to_bits() - 1);
+    assert!(NearlyEq::eq(&FloatCmp(below_two), &FloatCmp(2f32), &MaxUlps(1)));
+    assert_eq!(<FloatCmp<f32> as NearlyEq<_, MaxUlps>>::deviation(&FloatCmp(1f32), &FloatCmp(1.0 + 3.0 * f32::EPSILON)).unwrap().ulps, Some(3));
+
+    assert_eq!(NearlyEq::margin(&FloatCmp(1f64), &FloatCmp(1.25), &0.5), Some(0.5));
+    assert_eq!(NearlyEq::margin(&FloatCmp(1f64), &FloatCmp(1.0 + 2.0 * f64::EPSILON), &MaxUlps(4)), Some(0.5));
+    assert_nearly_eq!(vec![FloatCmp(1f64), FloatCmp(2.0)], vec![FloatCmp(1.0 + 1e-9), FloatCmp(2.0)]);
+    assert_eq!(format!("{:?}", FloatCmp(1.5f64)), "1.5");
+}
+
+#[test]
+#[cfg(feature = "float")]
+#[should_panic(expected = "assertion failed: `(left == right)` (left: `1.0` , right: `1.001`)")]
+fn bad_compare_generic_floats() {
+    use nearly_eq::FloatCmp;
+    assert_nearly_eq!(FloatCmp(1f64), FloatCmp(1.001));
+}
+
+#[test]
+#[cfg(feature = "approx")]
+fn compare_approx_values() {
+    use nearly_eq::{Approx, ApproxRelative, ApproxUlps, NearlyEq};
+
+    assert_nearly_eq!(Approx(0.1f64 + 0.2), Approx(0.3));
+    assert_nearly_eq!(Approx(1f32), Approx(1.05), 0.1);
+    assert!(NearlyEq::ne(&Approx(1f64), &Approx(1.2), &0.1));
+    assert_nearly_eq!(vec![Approx(1f64), Approx(2.0)], vec![Approx(1.0 + 1e-16), Approx(2.0)]);
+
+    assert_nearly_eq!(ApproxRelative(1e10f64), ApproxRelative(1e10 + 1.0), 1e-9);
+    assert!(NearlyEq::ne(&ApproxRelative(1e10f64), &ApproxRelative(1e10 + 100.0), &1e-9));
+    assert_nearly_eq!(ApproxUlps(1f32), ApproxUlps(1.0 + f32::EPSILON), 1);
+    assert!(NearlyEq::ne(&ApproxUlps(1f32), &ApproxUlps(1.0 + 2.0 * f32::EPSILON), &1));
+    assert_nearly_eq!(ApproxUlps(1f64), ApproxUlps(1.0 + 4.0 * f64::EPSILON));
+
+    assert_eq!(format!("{:?}", Approx(1.5f64)), "1.5");
+    assert_eq!(Approx(1.5f64).into_inner(), 1.5);
+}
+
+#[test]
+#[cfg(feature = "approx")]
+fn compare_nearly_with_approx_macros() {
+    use nearly_eq::Nearly;
+
+    assert_abs_diff_eq!(Nearly(0.1f64 + 0.2), Nearly(0.3f64));
+    assert_abs_diff_eq!(Nearly(vec![1i32, 5]), Nearly(vec![2i32, 4]), epsilon = 2);
+    assert_abs_diff_ne!(Nearly(1f64), Nearly(1.2f64), epsilon = 0.1);
+    assert!(abs_diff_eq!(Nearly(1f64), Nearly(1.05f64), epsilon = 0.1));
+}
+
+#[test]
+#[cfg(feature = "approx")]
+#[should_panic(expected = "assertion failed: `(left == right)` (left: `1.0` , right: `1.2`, eps: `0.1`)")]
+fn bad_compare_approx_values() {
+    use nearly_eq::Approx;
+    assert_nearly_eq!(Approx(1f64), Approx(1.2), 0.1);
+}
+
+#[test]
+fn compare_with_default_diff_without_debug() {
+    use nearly_eq::{DefaultTolerance, NearlyEq};
+
+    struct Tol(f64);
+
+    #[derive(Debug)]
+    struct Reading(f64);
+
+    impl NearlyEq<Reading, Tol> for Reading {
+        fn eq(&self, other: &Reading, eps: &Tol) -> bool {
+            (self.0 - other.0).abs() < eps.0
+        }
+    }
+
+    impl DefaultTolerance<Tol> for Reading {
+        fn default_tolerance() -> Tol {
+            Tol(0.5)
+        }
+    }
+
+    assert_nearly_eq!(Reading(1.0), Reading(1.25));
+}
+
+#[test]
+#[should_panic(expected = "percentile must be in [0, 100]")]
+fn outliers_percentile_out_of_range() {
+    let left = vec![1f64, 2.0];
+    let _ = nearly_eq::Outliers::percentile(&left, 101.0);
+}
+
+#[test]
+#[should_panic(expected = "percent must be in [0, 100]")]
+fn outliers_percent_out_of_range() {
+    let left = vec![1f64, 2.0];
+    let _ = nearly_eq::Outliers::at_most_percent(&left, -1.0);
+}
@@ -0,0 +1,39 @@
+#[macro_use]
+extern crate nearly_eq;
+
+struct Ignore;
+
+impl nearly_eq::Reporter for Ignore {
+    fn report(&self, _: &nearly_eq::Comparison) {}
+}
+
+// Near-miss collection is process-global, so it is tested in a single test of its own binary.
+#[test]
+fn near_misses_are_collected_per_call_site() {
+    std::env::set_var("NEARLY_EQ_NEAR_MISS", "0.5");
+    nearly_eq::set_reporter(Box::new(Ignore));
+    nearly_eq::take_reporter();
+    assert_nearly_eq!(1f64, 1.375f64, 0.5f64);
+    let near_misses = nearly_eq::near_misses();
+    assert_eq!(near_misses.len(), 1);
+    assert_eq!(near_misses[0].max_margin, 0.75);
+
+    nearly_eq::enable_near_miss(0.9);
+    for _ in 0..3 {
+        assert_nearly_eq!(1f64, 1.46875f64, 0.5f64);
+    }
+    assert_nearly_eq!(1f64, 1.25f64, 0.5f64);
+    nearly_eq::disable_near_miss();
+    let near_misses = nearly_eq::near_misses();
+    assert_eq!(near_misses.len(), 2);
+    assert_eq!(near_misses[0].count, 3);
+    assert_eq!(near_misses[0].max_margin, 0.9375);
+
+    nearly_eq::enable_near_miss(0.5);
+    assert_nearly_le!(1.4375f64, 1f64, 0.5f64);
+    assert_nearly_ge!(1.4375f64, 1f64, 0.5f64);
+    nearly_eq::disable_near_miss();
+    let near_misses = nearly_eq::near_misses();
+    assert_eq!(near_misses.len(), 3);
+    assert_eq!(near_misses[1].max_margin, 0.875);
+}